@@ -0,0 +1,9 @@
+-- One row per device/session instead of one session per user
+
+ALTER TABLE Sessions_
+  ADD INDEX idx_sessions_user_id (user_id),
+  DROP INDEX user_id;
+
+-- Public handle of a session, so the session ID itself never leaves its own cookie
+ALTER TABLE Sessions_
+  ADD COLUMN session_no INT NOT NULL AUTO_INCREMENT UNIQUE;
@@ -0,0 +1,7 @@
+-- Device metadata shown in the session list
+
+ALTER TABLE Sessions_
+  ADD COLUMN created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  ADD COLUMN last_used_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  ADD COLUMN user_agent VARCHAR(512) NOT NULL DEFAULT '',
+  ADD COLUMN ip_address VARCHAR(64) NOT NULL DEFAULT '';
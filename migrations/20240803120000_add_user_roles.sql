@@ -0,0 +1,8 @@
+-- Admin role and account disabling
+
+ALTER TABLE Users_
+  ADD COLUMN is_admin BOOLEAN NOT NULL DEFAULT false,
+  ADD COLUMN is_disabled BOOLEAN NOT NULL DEFAULT false;
+
+-- The seeded admin account keeps its privileges
+UPDATE Users_ SET is_admin = true WHERE user_id = 1;
@@ -0,0 +1,12 @@
+-- Replace the writeable flag of group members with a role
+
+ALTER TABLE GroupUserMapping_
+  ADD COLUMN role VARCHAR(16) NOT NULL DEFAULT 'viewer';
+
+UPDATE GroupUserMapping_ SET role = 'editor' WHERE writeable = true;
+
+UPDATE GroupUserMapping_ gum
+JOIN Groups_ g ON gum.group_id = g.group_id AND gum.user_id = g.owner_user_id
+SET gum.role = 'owner';
+
+ALTER TABLE GroupUserMapping_ DROP COLUMN writeable;
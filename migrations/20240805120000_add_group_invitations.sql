@@ -0,0 +1,16 @@
+-- Invitations to join a group, accepted or declined by the invitee
+
+CREATE TABLE GroupInvitations_ (
+  invitation_id INT AUTO_INCREMENT PRIMARY KEY,
+  group_id INT NOT NULL,
+  invitee_user_id INT NOT NULL,
+  inviter_user_id INT NOT NULL,
+  role VARCHAR(16) NOT NULL DEFAULT 'viewer',
+  status VARCHAR(16) NOT NULL DEFAULT 'pending', -- pending, accepted or declined
+  created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  expires_at TIMESTAMP NOT NULL,
+  FOREIGN KEY (group_id) REFERENCES Groups_(group_id) ON DELETE CASCADE,
+  FOREIGN KEY (invitee_user_id) REFERENCES Users_(user_id) ON DELETE CASCADE,
+  FOREIGN KEY (inviter_user_id) REFERENCES Users_(user_id) ON DELETE CASCADE,
+  INDEX idx_group_invitations_invitee (invitee_user_id, status)
+);
@@ -0,0 +1,15 @@
+-- Shareable links to join a group
+
+CREATE TABLE GroupInviteLinks_ (
+  link_id INT AUTO_INCREMENT PRIMARY KEY,
+  group_id INT NOT NULL,
+  token VARCHAR(64) UNIQUE NOT NULL,
+  role VARCHAR(16) NOT NULL DEFAULT 'viewer', -- role given to users joining through the link
+  created_by_user_id INT NOT NULL,
+  created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  expires_at TIMESTAMP NULL, -- NULL never expires
+  max_uses INT NULL, -- NULL is unlimited
+  use_count INT NOT NULL DEFAULT 0,
+  FOREIGN KEY (group_id) REFERENCES Groups_(group_id) ON DELETE CASCADE,
+  FOREIGN KEY (created_by_user_id) REFERENCES Users_(user_id) ON DELETE CASCADE
+);
@@ -0,0 +1,5 @@
+-- How double-booked workers are handled when tasks are added or updated:
+-- 'warning' reports the clashing tasks, 'strict' rejects the change
+
+ALTER TABLE Groups_
+  ADD COLUMN conflict_mode VARCHAR(16) NOT NULL DEFAULT 'warning';
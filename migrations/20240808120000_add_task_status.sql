@@ -0,0 +1,5 @@
+-- Status and percent complete of tasks
+
+ALTER TABLE Tasks_
+  ADD COLUMN status VARCHAR(32) NOT NULL DEFAULT 'todo', -- todo, in_progress, blocked or done
+  ADD COLUMN progress INT NOT NULL DEFAULT 0; -- percent complete, 0 to 100
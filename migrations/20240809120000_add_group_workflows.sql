@@ -0,0 +1,21 @@
+-- Workflow of task statuses defined per group.
+-- Groups without any WorkflowStates_ rows use the built-in statuses
+-- (todo, in_progress, blocked, done) with every transition allowed.
+
+CREATE TABLE WorkflowStates_ (
+  state_id INT AUTO_INCREMENT PRIMARY KEY,
+  group_id INT NOT NULL,
+  state_name VARCHAR(32) NOT NULL,
+  position INT NOT NULL, -- order of the state in the workflow, the first state is given to new tasks
+  UNIQUE (group_id, state_name),
+  FOREIGN KEY (group_id) REFERENCES Groups_(group_id) ON DELETE CASCADE
+);
+
+CREATE TABLE WorkflowTransitions_ (
+  transition_id INT AUTO_INCREMENT PRIMARY KEY,
+  group_id INT NOT NULL,
+  from_state VARCHAR(32) NOT NULL,
+  to_state VARCHAR(32) NOT NULL,
+  UNIQUE (group_id, from_state, to_state),
+  FOREIGN KEY (group_id) REFERENCES Groups_(group_id) ON DELETE CASCADE
+);
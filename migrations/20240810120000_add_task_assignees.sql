@@ -0,0 +1,12 @@
+-- Several workers per task. Tasks_.worker_user_id keeps the first assignee for older clients.
+
+CREATE TABLE TaskAssignees_ (
+  task_id INT NOT NULL,
+  user_id INT NOT NULL,
+  PRIMARY KEY (task_id, user_id),
+  FOREIGN KEY (task_id) REFERENCES Tasks_(task_id) ON DELETE CASCADE,
+  FOREIGN KEY (user_id) REFERENCES Users_(user_id) ON DELETE CASCADE
+);
+
+INSERT INTO TaskAssignees_ (task_id, user_id)
+SELECT task_id, worker_user_id FROM Tasks_ WHERE worker_user_id IS NOT NULL;
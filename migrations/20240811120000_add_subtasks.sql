@@ -0,0 +1,5 @@
+-- Parent/child tasks inside a project. Deleting a task deletes its subtasks.
+
+ALTER TABLE Tasks_
+  ADD COLUMN parent_task_id INT NULL, -- NULL for top level tasks
+  ADD FOREIGN KEY (parent_task_id) REFERENCES Tasks_(task_id) ON DELETE CASCADE;
@@ -0,0 +1,13 @@
+-- Discussion attached to a task
+
+CREATE TABLE TaskComments_ (
+  comment_id INT AUTO_INCREMENT PRIMARY KEY,
+  task_id INT NOT NULL,
+  author_user_id INT NOT NULL,
+  content TEXT NOT NULL,
+  created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  updated_at TIMESTAMP NULL, -- NULL until the comment is edited
+  FOREIGN KEY (task_id) REFERENCES Tasks_(task_id) ON DELETE CASCADE,
+  FOREIGN KEY (author_user_id) REFERENCES Users_(user_id) ON DELETE CASCADE,
+  INDEX (task_id, created_at)
+);
@@ -0,0 +1,24 @@
+-- In-app notifications and @user_name mentions in task comments
+
+CREATE TABLE Notifications_ (
+  notification_id INT AUTO_INCREMENT PRIMARY KEY,
+  user_id INT NOT NULL, -- recipient
+  kind VARCHAR(32) NOT NULL,
+  message VARCHAR(512) NOT NULL,
+  group_id INT NULL, -- group the notification is about, if any
+  task_id INT NULL, -- task the notification is about, if any
+  is_read BOOLEAN NOT NULL DEFAULT FALSE,
+  created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  FOREIGN KEY (user_id) REFERENCES Users_(user_id) ON DELETE CASCADE,
+  FOREIGN KEY (group_id) REFERENCES Groups_(group_id) ON DELETE SET NULL,
+  FOREIGN KEY (task_id) REFERENCES Tasks_(task_id) ON DELETE SET NULL,
+  INDEX (user_id, is_read)
+);
+
+CREATE TABLE CommentMentions_ (
+  comment_id INT NOT NULL,
+  user_id INT NOT NULL,
+  PRIMARY KEY (comment_id, user_id),
+  FOREIGN KEY (comment_id) REFERENCES TaskComments_(comment_id) ON DELETE CASCADE,
+  FOREIGN KEY (user_id) REFERENCES Users_(user_id) ON DELETE CASCADE
+);
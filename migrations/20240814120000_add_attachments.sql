@@ -0,0 +1,22 @@
+-- Files attached to projects and tasks. The files live under ATTACHMENT_DIR,
+-- named by storage_name; the total size per group is capped by attachment_quota_bytes.
+
+ALTER TABLE Groups_
+  ADD COLUMN attachment_quota_bytes BIGINT NOT NULL DEFAULT 104857600; -- 100 MiB
+
+CREATE TABLE Attachments_ (
+  attachment_id INT AUTO_INCREMENT PRIMARY KEY,
+  group_id INT NOT NULL,
+  project_id INT NOT NULL,
+  task_id INT NULL, -- NULL for files attached to the project itself
+  uploader_user_id INT NOT NULL,
+  file_name VARCHAR(255) NOT NULL, -- name given by the uploader
+  content_type VARCHAR(255) NOT NULL,
+  size_bytes BIGINT NOT NULL,
+  storage_name VARCHAR(64) UNIQUE NOT NULL, -- name of the file on disk
+  created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  FOREIGN KEY (group_id) REFERENCES Groups_(group_id) ON DELETE CASCADE,
+  FOREIGN KEY (project_id) REFERENCES Projects_(project_id) ON DELETE CASCADE,
+  FOREIGN KEY (task_id) REFERENCES Tasks_(task_id) ON DELETE CASCADE,
+  FOREIGN KEY (uploader_user_id) REFERENCES Users_(user_id) ON DELETE CASCADE
+);
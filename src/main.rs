@@ -33,6 +33,7 @@ async fn main() -> std::io::Result<()> {
             .configure(routes::routes::group_selection_configure)
             .configure(routes::routes::group_view_configure)
             .configure(routes::routes::project_view_configure)
+            .configure(routes::routes::notification_configure)
     })
     .bind(server_address)?
     .run()
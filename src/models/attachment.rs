@@ -0,0 +1,17 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct Attachment {
+    pub attachment_id: i32,
+    pub group_id: i32,
+    pub project_id: i32,
+    pub task_id: Option<i32>,
+    pub uploader_user_id: i32,
+    pub file_name: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub storage_name: String,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,8 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct CommentMention {
+    pub comment_id: i32,
+    pub user_id: i32,
+}
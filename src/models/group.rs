@@ -6,4 +6,30 @@ pub struct Group {
     pub group_id: i32,
     pub group_name: String,
     pub owner_user_id: i32,
+    pub conflict_mode: String,
+    pub attachment_quota_bytes: i64,
+}
+
+// Handling of workers booked on overlapping tasks, stored as lowercase text in Groups_.conflict_mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictMode {
+    Warning,
+    Strict,
+}
+
+impl ConflictMode {
+    pub fn parse(mode: &str) -> Option<ConflictMode> {
+        match mode {
+            "warning" => Some(ConflictMode::Warning),
+            "strict" => Some(ConflictMode::Strict),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ConflictMode::Warning => "warning",
+            ConflictMode::Strict => "strict",
+        }
+    }
 }
@@ -0,0 +1,15 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct GroupInvitation {
+    pub invitation_id: i32,
+    pub group_id: i32,
+    pub invitee_user_id: i32,
+    pub inviter_user_id: i32,
+    pub role: String,
+    pub status: String,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
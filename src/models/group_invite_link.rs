@@ -0,0 +1,16 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct GroupInviteLink {
+    pub link_id: i32,
+    pub group_id: i32,
+    pub token: String,
+    pub role: String,
+    pub created_by_user_id: i32,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub max_uses: Option<i32>,
+    pub use_count: i32,
+}
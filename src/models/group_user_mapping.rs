@@ -5,5 +5,65 @@ use sqlx::FromRow;
 pub struct GroupUserMapping {
     pub group_id: i32,
     pub user_id: i32,
-    pub writeable: bool,
+    pub role: String,
+}
+
+// Role of a member inside a group, stored as lowercase text in GroupUserMapping_.role
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GroupRole {
+    Owner,
+    Admin,
+    Editor,
+    Viewer,
+}
+
+// Actions guarded by the role of the member
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Permission {
+    ManageGroup,
+    ManageMembers,
+    ManageWorkflow,
+    ManageTags,
+    ManageProjects,
+    ManageTasks,
+    CommentTasks,
+}
+
+impl GroupRole {
+    pub fn parse(role: &str) -> Option<GroupRole> {
+        match role {
+            "owner" => Some(GroupRole::Owner),
+            "admin" => Some(GroupRole::Admin),
+            "editor" => Some(GroupRole::Editor),
+            "viewer" => Some(GroupRole::Viewer),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GroupRole::Owner => "owner",
+            GroupRole::Admin => "admin",
+            GroupRole::Editor => "editor",
+            GroupRole::Viewer => "viewer",
+        }
+    }
+
+    // Permission matrix:
+    //   owner  - everything, including updating and deleting the group
+    //   admin  - members, workflow, tags, projects and tasks
+    //   editor - tags, projects and tasks
+    //   viewer - read only, apart from commenting on tasks
+    pub fn allows(&self, permission: Permission) -> bool {
+        match self {
+            GroupRole::Owner => true,
+            GroupRole::Admin => permission != Permission::ManageGroup,
+            GroupRole::Editor => matches!(
+                permission,
+                Permission::ManageTags | Permission::ManageProjects | Permission::ManageTasks | Permission::CommentTasks
+            ),
+            GroupRole::Viewer => permission == Permission::CommentTasks,
+        }
+    }
 }
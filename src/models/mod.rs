@@ -4,8 +4,16 @@ pub mod user;
 pub mod session;
 pub mod group;
 pub mod group_user_mapping;
+pub mod group_invitation;
+pub mod group_invite_link;
 pub mod tag;
 pub mod project;
 pub mod tag_project_mapping;
 pub mod task;
+pub mod task_assignee;
+pub mod task_comment;
+pub mod comment_mention;
+pub mod notification;
+pub mod attachment;
 pub mod dependency;
+pub mod workflow;
@@ -0,0 +1,35 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct Notification {
+    pub notification_id: i32,
+    pub user_id: i32,
+    pub kind: String,
+    pub message: String,
+    pub group_id: Option<i32>,
+    pub task_id: Option<i32>,
+    pub is_read: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+// What a notification is about, stored as text in Notifications_.kind
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationKind {
+    Mention,
+    TaskAssigned,
+    TaskRescheduled,
+    GroupInvitation,
+}
+
+impl NotificationKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationKind::Mention => "mention",
+            NotificationKind::TaskAssigned => "task_assigned",
+            NotificationKind::TaskRescheduled => "task_rescheduled",
+            NotificationKind::GroupInvitation => "group_invitation",
+        }
+    }
+}
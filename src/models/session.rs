@@ -5,7 +5,12 @@ use sqlx::FromRow;
 #[derive(Debug, Serialize, Deserialize, FromRow)]
 pub struct Session {
     pub session_id: String,
+    pub session_no: i32,
     pub user_id: i32,
     pub expires_at: DateTime<Utc>,
     pub is_persistent: bool,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: DateTime<Utc>,
+    pub user_agent: String,
+    pub ip_address: String,
 }
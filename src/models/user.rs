@@ -7,4 +7,6 @@ pub struct User {
     pub user_name: String,
     pub user_email: String,
     pub password_hash: String,
+    pub is_admin: bool,
+    pub is_disabled: bool,
 }
@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct WorkflowState {
+    pub state_id: i32,
+    pub group_id: i32,
+    pub state_name: String,
+    pub position: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct WorkflowTransition {
+    pub transition_id: i32,
+    pub group_id: i32,
+    pub from_state: String,
+    pub to_state: String,
+}
@@ -1,14 +1,20 @@
 use actix_web::{web, HttpResponse, Responder};
 use sqlx::MySqlPool;
-use log::error;
-use super::admin_models::AdminDefaultResponse;
+use log::{error, info};
+use super::admin_models::{
+    AdminDefaultResponse,
+    GetUserListResponse, AdminUser,
+    DisableUserRequest,
+    PurgeUserSessionsRequest,
+    SetAttachmentQuotaRequest,
+};
 
 pub async fn session_reset(
     pool: web::Data<MySqlPool>,
 ) -> impl Responder {
-    // Attempt to delete all sessions from the Sessions_ table
+    // Attempt to delete all sessions from the Sessions_ table, admins stay logged in
     let result = sqlx::query!(
-        "DELETE FROM Sessions_ WHERE user_id NOT IN (1, 2)"
+        "DELETE FROM Sessions_ WHERE user_id NOT IN (SELECT user_id FROM Users_ WHERE is_admin = true)"
     )
     .execute(pool.get_ref())
     .await;
@@ -27,3 +33,240 @@ pub async fn session_reset(
         }
     }
 }
+
+pub async fn get_user_list(
+    pool: web::Data<MySqlPool>,
+) -> impl Responder {
+    let users_result = sqlx::query!(
+        "SELECT u.user_name, u.user_email,
+                u.is_admin AS `is_admin: bool`,
+                u.is_disabled AS `is_disabled: bool`,
+                COUNT(s.session_id) AS active_session_count
+         FROM Users_ u
+         LEFT JOIN Sessions_ s ON s.user_id = u.user_id AND s.expires_at > NOW()
+         GROUP BY u.user_id
+         ORDER BY u.user_id"
+    )
+    .fetch_all(pool.get_ref())
+    .await;
+
+    match users_result {
+        Ok(records) => {
+            let users: Vec<AdminUser> = records.into_iter().map(|record| AdminUser {
+                user_name: record.user_name,
+                user_email: record.user_email,
+                is_admin: record.is_admin,
+                is_disabled: record.is_disabled,
+                active_session_count: record.active_session_count,
+            }).collect();
+
+            HttpResponse::Ok().json(GetUserListResponse { users })
+        }
+        Err(e) => {
+            error!("Failed to fetch user list: {}", e);
+            HttpResponse::InternalServerError().json(GetUserListResponse { users: Vec::new() })
+        }
+    }
+}
+
+// Disable (or re-enable) an account, a disabled account also loses all of its sessions
+pub async fn disable_user(
+    pool: web::Data<MySqlPool>,
+    request: web::Json<DisableUserRequest>,
+) -> impl Responder {
+    let user_name = &request.user_name;
+    let disabled = request.disabled;
+
+    let user_result = sqlx::query!(
+        "SELECT user_id, is_admin AS `is_admin: bool` FROM Users_ WHERE user_name = ?",
+        user_name
+    )
+    .fetch_one(pool.get_ref())
+    .await;
+
+    let user = match user_result {
+        Ok(record) => record,
+        Err(_) => {
+            info!("User not found: {}", user_name);
+            return HttpResponse::BadRequest().json(AdminDefaultResponse {
+                success: false,
+                message: "User not found".into(),
+            });
+        }
+    };
+
+    if user.is_admin && disabled {
+        return HttpResponse::BadRequest().json(AdminDefaultResponse {
+            success: false,
+            message: "Admin accounts cannot be disabled".into(),
+        });
+    }
+
+    let mut tx = match pool.begin().await {
+        Ok(transaction) => transaction,
+        Err(e) => {
+            error!("Failed to start transaction: {}", e);
+            return HttpResponse::InternalServerError().json(AdminDefaultResponse {
+                success: false,
+                message: "Failed to start transaction".into(),
+            });
+        }
+    };
+
+    let update_result = sqlx::query!(
+        "UPDATE Users_ SET is_disabled = ? WHERE user_id = ?",
+        disabled, user.user_id
+    )
+    .execute(&mut *tx)
+    .await;
+
+    if let Err(e) = update_result {
+        error!("Failed to update account state of user {}: {}", user_name, e);
+        let _ = tx.rollback().await;
+        return HttpResponse::InternalServerError().json(AdminDefaultResponse {
+            success: false,
+            message: "Failed to update account".into(),
+        });
+    }
+
+    if disabled {
+        let delete_sessions_result = sqlx::query!(
+            "DELETE FROM Sessions_ WHERE user_id = ?",
+            user.user_id
+        )
+        .execute(&mut *tx)
+        .await;
+
+        if let Err(e) = delete_sessions_result {
+            error!("Failed to delete sessions of user {}: {}", user_name, e);
+            let _ = tx.rollback().await;
+            return HttpResponse::InternalServerError().json(AdminDefaultResponse {
+                success: false,
+                message: "Failed to delete sessions".into(),
+            });
+        }
+    }
+
+    if let Err(e) = tx.commit().await {
+        error!("Failed to commit transaction: {}", e);
+        return HttpResponse::InternalServerError().json(AdminDefaultResponse {
+            success: false,
+            message: "Failed to commit transaction".into(),
+        });
+    }
+
+    info!("User {} disabled: {}", user_name, disabled);
+    HttpResponse::Ok().json(AdminDefaultResponse {
+        success: true,
+        message: if disabled {
+            "User disabled successfully".into()
+        } else {
+            "User enabled successfully".into()
+        },
+    })
+}
+
+pub async fn purge_user_sessions(
+    pool: web::Data<MySqlPool>,
+    request: web::Json<PurgeUserSessionsRequest>,
+) -> impl Responder {
+    let user_name = &request.user_name;
+
+    let result = sqlx::query!(
+        "DELETE s FROM Sessions_ s
+         JOIN Users_ u ON s.user_id = u.user_id
+         WHERE u.user_name = ?",
+        user_name
+    )
+    .execute(pool.get_ref())
+    .await;
+
+    match result {
+        Ok(result) => {
+            info!("Purged {} sessions of user {}", result.rows_affected(), user_name);
+            HttpResponse::Ok().json(AdminDefaultResponse {
+                success: true,
+                message: format!("{} sessions have been purged", result.rows_affected()),
+            })
+        }
+        Err(e) => {
+            error!("Failed to purge sessions of user {}: {}", user_name, e);
+            HttpResponse::InternalServerError().json(AdminDefaultResponse {
+                success: false,
+                message: "Failed to purge sessions".into(),
+            })
+        }
+    }
+}
+
+pub async fn set_attachment_quota(
+    pool: web::Data<MySqlPool>,
+    request: web::Json<SetAttachmentQuotaRequest>,
+) -> impl Responder {
+    let owner_user_name = &request.owner_user_name;
+    let group_name = &request.group_name;
+    let quota_bytes = request.quota_bytes;
+
+    if quota_bytes < 0 {
+        return HttpResponse::BadRequest().json(AdminDefaultResponse {
+            success: false,
+            message: "Quota must not be negative".into(),
+        });
+    }
+
+    let result = sqlx::query!(
+        "UPDATE Groups_ g
+         JOIN Users_ u ON g.owner_user_id = u.user_id
+         SET g.attachment_quota_bytes = ?
+         WHERE u.user_name = ? AND g.group_name = ?",
+        quota_bytes, owner_user_name, group_name
+    )
+    .execute(pool.get_ref())
+    .await;
+
+    match result {
+        Ok(result) if result.rows_affected() == 0 => {
+            // MySQL counts only changed rows, so check whether the group exists at all
+            let exists = sqlx::query!(
+                "SELECT g.group_id FROM Groups_ g
+                 JOIN Users_ u ON g.owner_user_id = u.user_id
+                 WHERE u.user_name = ? AND g.group_name = ?",
+                owner_user_name, group_name
+            )
+            .fetch_optional(pool.get_ref())
+            .await;
+
+            match exists {
+                Ok(Some(_)) => HttpResponse::Ok().json(AdminDefaultResponse {
+                    success: true,
+                    message: "Attachment quota updated".into(),
+                }),
+                Ok(None) => HttpResponse::NotFound().json(AdminDefaultResponse {
+                    success: false,
+                    message: "Group not found".into(),
+                }),
+                Err(e) => {
+                    error!("Failed to fetch group {}/{}: {}", owner_user_name, group_name, e);
+                    HttpResponse::InternalServerError().json(AdminDefaultResponse {
+                        success: false,
+                        message: "Failed to update attachment quota".into(),
+                    })
+                }
+            }
+        }
+        Ok(_) => {
+            info!("Attachment quota of group {}/{} set to {} bytes", owner_user_name, group_name, quota_bytes);
+            HttpResponse::Ok().json(AdminDefaultResponse {
+                success: true,
+                message: "Attachment quota updated".into(),
+            })
+        }
+        Err(e) => {
+            error!("Failed to set attachment quota of group {}/{}: {}", owner_user_name, group_name, e);
+            HttpResponse::InternalServerError().json(AdminDefaultResponse {
+                success: false,
+                message: "Failed to update attachment quota".into(),
+            })
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use std::future::{ready, Future, Ready};
+use std::pin::Pin;
+use std::rc::Rc;
+use actix_web::{
+    body::EitherBody,
+    dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
+    web, Error, HttpResponse, ResponseError,
+};
+use sqlx::MySqlPool;
+use log::{error, info};
+use crate::routes::auth::auth_extractors::authenticate;
+use super::admin_models::AdminDefaultResponse;
+
+// Middleware that only lets requests from an active admin session through
+pub struct AdminGuard;
+
+impl<S, B> Transform<S, ServiceRequest> for AdminGuard
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Transform = AdminGuardMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(AdminGuardMiddleware {
+            service: Rc::new(service),
+        }))
+    }
+}
+
+pub struct AdminGuardMiddleware<S> {
+    service: Rc<S>,
+}
+
+impl<S, B> Service<ServiceRequest> for AdminGuardMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>>>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = Rc::clone(&self.service);
+
+        Box::pin(async move {
+            let session_id = match req.cookie("session_id") {
+                Some(cookie) => cookie.value().to_string(),
+                None => {
+                    info!("Session ID not found in cookies for admin request {}", req.path());
+                    let response = HttpResponse::Unauthorized().json(AdminDefaultResponse {
+                        success: false,
+                        message: "Session ID not found".into(),
+                    });
+                    return Ok(req.into_response(response).map_into_right_body());
+                }
+            };
+
+            let pool = match req.app_data::<web::Data<MySqlPool>>() {
+                Some(pool) => pool.clone(),
+                None => {
+                    error!("Database pool is not configured for admin scope");
+                    let response = HttpResponse::InternalServerError().json(AdminDefaultResponse {
+                        success: false,
+                        message: "Failed to check admin privileges".into(),
+                    });
+                    return Ok(req.into_response(response).map_into_right_body());
+                }
+            };
+
+            match authenticate(pool.get_ref(), &session_id).await {
+                Ok(current_user) if current_user.is_admin => {
+                    info!("Admin {} requested {}", current_user.user_name, req.path());
+                    service.call(req).await.map(ServiceResponse::map_into_left_body)
+                }
+                Ok(current_user) => {
+                    info!("Non-admin user {} refused on {}", current_user.user_name, req.path());
+                    let response = HttpResponse::Forbidden().json(AdminDefaultResponse {
+                        success: false,
+                        message: "Admin privileges required".into(),
+                    });
+                    Ok(req.into_response(response).map_into_right_body())
+                }
+                Err(e) => {
+                    let response = HttpResponse::build(e.status_code()).json(AdminDefaultResponse {
+                        success: false,
+                        message: e.to_string(),
+                    });
+                    Ok(req.into_response(response).map_into_right_body())
+                }
+            }
+        })
+    }
+}
@@ -9,3 +9,35 @@ pub struct AdminDefaultResponse {
     pub success: bool,
     pub message: String,
 }
+
+#[derive(Serialize)]
+pub struct AdminUser {
+    pub user_name: String,
+    pub user_email: String,
+    pub is_admin: bool,
+    pub is_disabled: bool,
+    pub active_session_count: i64,
+}
+
+#[derive(Serialize)]
+pub struct GetUserListResponse {
+    pub users: Vec<AdminUser>,
+}
+
+#[derive(Deserialize)]
+pub struct DisableUserRequest {
+    pub user_name: String,
+    pub disabled: bool,
+}
+
+#[derive(Deserialize)]
+pub struct PurgeUserSessionsRequest {
+    pub user_name: String,
+}
+
+#[derive(Deserialize)]
+pub struct SetAttachmentQuotaRequest {
+    pub owner_user_name: String,
+    pub group_name: String,
+    pub quota_bytes: i64,
+}
@@ -1,3 +1,4 @@
 
 pub mod admin_handlers;
+pub mod admin_middleware;
 pub mod admin_models;
\ No newline at end of file
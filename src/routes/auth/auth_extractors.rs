@@ -0,0 +1,208 @@
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use actix_web::{
+    dev::Payload, http::StatusCode, web, FromRequest, HttpRequest, HttpResponse, ResponseError,
+};
+use serde::Serialize;
+use sqlx::MySqlPool;
+use log::{error, info};
+use crate::models::group::ConflictMode;
+use crate::models::group_user_mapping::{GroupRole, Permission};
+
+// User authenticated by the session_id cookie of the request
+pub struct CurrentUser {
+    pub user_id: i32,
+    pub user_name: String,
+    pub session_id: String,
+    pub is_admin: bool,
+}
+
+#[derive(Debug)]
+pub enum AuthError {
+    MissingSession,
+    InvalidSession,
+    Internal,
+}
+
+#[derive(Serialize)]
+struct AuthErrorResponse {
+    success: bool,
+    message: String,
+}
+
+impl fmt::Display for AuthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AuthError::MissingSession => write!(f, "Session ID not found"),
+            AuthError::InvalidSession => write!(f, "Invalid or expired session ID"),
+            AuthError::Internal => write!(f, "Failed to validate session"),
+        }
+    }
+}
+
+impl ResponseError for AuthError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            AuthError::MissingSession | AuthError::InvalidSession => StatusCode::UNAUTHORIZED,
+            AuthError::Internal => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::build(self.status_code()).json(AuthErrorResponse {
+            success: false,
+            message: self.to_string(),
+        })
+    }
+}
+
+// Resolve a session ID to its (enabled) user and record the use of the session
+pub async fn authenticate(pool: &MySqlPool, session_id: &str) -> Result<CurrentUser, AuthError> {
+    let session_result = sqlx::query!(
+        "SELECT u.user_id, u.user_name, u.is_admin AS `is_admin: bool`
+         FROM Sessions_ s
+         JOIN Users_ u ON s.user_id = u.user_id
+         WHERE s.session_id = ? AND s.expires_at > NOW() AND u.is_disabled = false",
+        session_id
+    )
+    .fetch_optional(pool)
+    .await;
+
+    let user = match session_result {
+        Ok(Some(record)) => record,
+        Ok(None) => {
+            info!("Invalid or expired session ID: {}", session_id);
+            return Err(AuthError::InvalidSession);
+        }
+        Err(e) => {
+            error!("Failed to validate session ID {}: {}", session_id, e);
+            return Err(AuthError::Internal);
+        }
+    };
+
+    let touch_result = sqlx::query!(
+        "UPDATE Sessions_ SET last_used_at = NOW() WHERE session_id = ?",
+        session_id
+    )
+    .execute(pool)
+    .await;
+
+    if let Err(e) = touch_result {
+        error!("Failed to update last use of session ID {}: {}", session_id, e);
+    }
+
+    Ok(CurrentUser {
+        user_id: user.user_id,
+        user_name: user.user_name,
+        session_id: session_id.to_string(),
+        is_admin: user.is_admin,
+    })
+}
+
+impl FromRequest for CurrentUser {
+    type Error = AuthError;
+    type Future = Pin<Box<dyn Future<Output = Result<Self, Self::Error>>>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        let session_id = req.cookie("session_id").map(|cookie| cookie.value().to_string());
+        let pool = req.app_data::<web::Data<MySqlPool>>().cloned();
+        let path = req.path().to_string();
+
+        Box::pin(async move {
+            let session_id = match session_id {
+                Some(session_id) => session_id,
+                None => {
+                    info!("Session ID not found in cookies for {}", path);
+                    return Err(AuthError::MissingSession);
+                }
+            };
+
+            let pool = match pool {
+                Some(pool) => pool,
+                None => {
+                    error!("Database pool is not configured for {}", path);
+                    return Err(AuthError::Internal);
+                }
+            };
+
+            authenticate(pool.get_ref(), &session_id).await
+        })
+    }
+}
+
+// Membership of the current user in the group addressed by (owner_user_name, group_name)
+pub struct GroupMembership {
+    pub group_id: i32,
+    pub owner_user_id: i32,
+    pub role: GroupRole,
+    pub conflict_mode: ConflictMode,
+}
+
+impl GroupMembership {
+    pub fn can(&self, permission: Permission) -> bool {
+        self.role.allows(permission)
+    }
+}
+
+#[derive(Debug)]
+pub enum GroupAccessError {
+    GroupNotFound,
+    NotMember,
+    Database(sqlx::Error),
+}
+
+impl fmt::Display for GroupAccessError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GroupAccessError::GroupNotFound => write!(f, "Group not found"),
+            GroupAccessError::NotMember => write!(f, "Not a member of this group"),
+            GroupAccessError::Database(_) => write!(f, "Failed to check group membership"),
+        }
+    }
+}
+
+impl GroupAccessError {
+    pub fn status_code(&self) -> StatusCode {
+        match self {
+            GroupAccessError::GroupNotFound => StatusCode::BAD_REQUEST,
+            GroupAccessError::NotMember => StatusCode::FORBIDDEN,
+            GroupAccessError::Database(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+pub async fn require_group_member(
+    pool: &MySqlPool,
+    current_user: &CurrentUser,
+    owner_user_name: &str,
+    group_name: &str,
+) -> Result<GroupMembership, GroupAccessError> {
+    let membership_result = sqlx::query!(
+        "SELECT g.group_id, g.owner_user_id, g.conflict_mode,
+                gum.user_id AS `member_user_id?: i32`,
+                gum.role AS `role?: String`
+         FROM Groups_ g
+         JOIN Users_ u ON g.owner_user_id = u.user_id
+         LEFT JOIN GroupUserMapping_ gum ON gum.group_id = g.group_id AND gum.user_id = ?
+         WHERE g.group_name = ? AND u.user_name = ?",
+        current_user.user_id, group_name, owner_user_name
+    )
+    .fetch_optional(pool)
+    .await;
+
+    match membership_result {
+        Ok(Some(record)) if record.member_user_id.is_some() => Ok(GroupMembership {
+            group_id: record.group_id,
+            owner_user_id: record.owner_user_id,
+            role: record.role.as_deref().and_then(GroupRole::parse).unwrap_or(GroupRole::Viewer),
+            conflict_mode: ConflictMode::parse(&record.conflict_mode).unwrap_or(ConflictMode::Warning),
+        }),
+        Ok(Some(_)) => Err(GroupAccessError::NotMember),
+        Ok(None) => Err(GroupAccessError::GroupNotFound),
+        Err(e) => {
+            error!("Failed to check membership of {} in group {}: {}", current_user.user_name, group_name, e);
+            Err(GroupAccessError::Database(e))
+        }
+    }
+}
@@ -1,11 +1,21 @@
-use actix_web::{web, HttpResponse, HttpRequest, Responder};
+use actix_web::{web, HttpResponse, Responder};
 use sqlx::MySqlPool;
 use log::{error, info};
+use crate::models::group::ConflictMode;
+use crate::models::group_user_mapping::{GroupRole, Permission};
+use crate::routes::auth::auth_extractors::{CurrentUser, require_group_member};
+use crate::routes::project_view::project_view_storage::remove_attachment_files;
 use super::group_selection_models::{
     GetGroupListRequest, GetGroupListResponse, Group,
     AddGroupRequest, AddGroupResponse,
     UpdateGroupRequest, UpdateGroupResponse,
     DeleteGroupRequest, DeleteGroupResponse,
+    TransferOwnershipRequest, TransferOwnershipResponse,
+    GetInvitationListRequest, GetInvitationListResponse, Invitation,
+    AcceptInvitationRequest, AcceptInvitationResponse,
+    DeclineInvitationRequest, DeclineInvitationResponse,
+    JoinGroupRequest, JoinGroupResponse,
+    GetMyTaskListRequest, GetMyTaskListResponse, MyTask,
 };
 
 // Default handler for group selection root
@@ -16,7 +26,7 @@ pub async fn group_selection_get() -> impl Responder {
 // Handler to get the group list
 pub async fn get_group_list(
     pool: web::Data<MySqlPool>,
-    req: HttpRequest,
+    current_user: CurrentUser,
     _: web::Json<GetGroupListRequest>,
 ) -> impl Responder {
     // Initialize an empty group list response
@@ -25,36 +35,11 @@ pub async fn get_group_list(
         groups: Vec::new(),
     };
 
-    // Extract session ID from the cookie
-    let session_id = match req.cookie("session_id") {
-        Some(cookie) => cookie.value().to_string(),
-        None => {
-            info!("Session ID not found in cookies for get_group_list");
-            return HttpResponse::BadRequest().json(response); // Return empty response in case of missing session ID
-        }
-    };
-
-    info!("Received request to get group list with session ID: {}", session_id);
-
-    // Verify session ID and get user information
-    let session_result = sqlx::query!(
-        "SELECT user_id FROM Sessions_ WHERE session_id = ? AND expires_at > NOW()",
-        session_id
-    )
-    .fetch_one(pool.get_ref())
-    .await;
-
-    let user_id = match session_result {
-        Ok(session) => session.user_id,
-        Err(_) => {
-            info!("Invalid or expired session ID: {}", session_id);
-            return HttpResponse::BadRequest().json(response); // Return empty response in case of invalid session ID
-        }
-    };
+    let user_id = current_user.user_id;
 
     // Fetch the groups and their details in a single query
     let groups_result = sqlx::query!(
-        "SELECT g.group_name, u.user_name AS owner_username, gum.writeable 
+        "SELECT g.group_name, u.user_name AS owner_username, g.conflict_mode, gum.role 
          FROM GroupUserMapping_ gum
          JOIN Groups_ g ON gum.group_id = g.group_id
          JOIN Users_ u ON g.owner_user_id = u.user_id
@@ -66,10 +51,15 @@ pub async fn get_group_list(
 
     match groups_result {
         Ok(records) => {
-            response.groups = records.into_iter().map(|record| Group {
-                group_name: record.group_name,
-                writeable: record.writeable.unwrap_or(0) != 0, // Convert Option<i8> to bool
-                owner_username: record.owner_username,
+            response.groups = records.into_iter().map(|record| {
+                let role = GroupRole::parse(&record.role).unwrap_or(GroupRole::Viewer);
+                Group {
+                    group_name: record.group_name,
+                    writeable: role.allows(Permission::ManageTasks),
+                    role: role.as_str().to_string(),
+                    owner_username: record.owner_username,
+                    conflict_mode: record.conflict_mode,
+                }
             }).collect();
 
             HttpResponse::Ok().json(response)
@@ -83,7 +73,7 @@ pub async fn get_group_list(
 
 pub async fn add_group(
     pool: web::Data<MySqlPool>,
-    req: HttpRequest,
+    current_user: CurrentUser,
     group_info: web::Json<AddGroupRequest>,
 ) -> impl Responder {
     // Initialize an empty response
@@ -92,33 +82,8 @@ pub async fn add_group(
         message: String::new(),
     };
 
-    // Extract session ID from the cookie
-    let session_id = match req.cookie("session_id") {
-        Some(cookie) => cookie.value().to_string(),
-        None => {
-            info!("Session ID not found in cookies for add_group");
-            response.message = "Session ID not found".to_string();
-            return HttpResponse::BadRequest().json(response);
-        }
-    };
-    info!("Received request to add group with session ID: {}", session_id);
-
-    // Verify session ID and get user information
-    let session_result = sqlx::query!(
-        "SELECT user_id FROM Sessions_ WHERE session_id = ? AND expires_at > NOW()",
-        session_id
-    )
-    .fetch_one(pool.get_ref())
-    .await;
-
-    let user_id = match session_result {
-        Ok(session) => session.user_id,
-        Err(_) => {
-            info!("Invalid or expired session ID: {}", session_id);
-            response.message = "Invalid or expired session ID".to_string();
-            return HttpResponse::BadRequest().json(response);
-        }
-    };
+    let user_id = current_user.user_id;
+    info!("Received request to add group from user {}", current_user.user_name);
 
     // Insert the new group into Groups_ table
     let group_name = &group_info.group_name;
@@ -154,7 +119,7 @@ pub async fn add_group(
 
     // Insert the user into GroupUserMapping_ table as the owner
     let insert_mapping_result = sqlx::query!(
-        "INSERT INTO GroupUserMapping_ (group_id, user_id, writeable) VALUES (?, ?, true)",
+        "INSERT INTO GroupUserMapping_ (group_id, user_id, role) VALUES (?, ?, 'owner')",
         group_id, user_id
     )
     .execute(pool.get_ref())
@@ -174,80 +139,53 @@ pub async fn add_group(
 
 pub async fn update_group(
     pool: web::Data<MySqlPool>,
-    req: HttpRequest,
+    current_user: CurrentUser,
     request: web::Json<UpdateGroupRequest>,
 ) -> impl Responder {
     let owner_user_name = &request.owner_user_name;
     let group_name = &request.group_name;
     let new_group_name = &request.new_group_name;
+    let new_conflict_mode = &request.new_conflict_mode;
 
-    // Get the current user name using session ID in the cookie
-    let session_id = match req.cookie("session_id") {
-        Some(cookie) => cookie.value().to_string(),
-        None => {
-            info!("Session ID not found in cookies for delete_tag");
-            return HttpResponse::BadRequest().json(UpdateGroupResponse {
-                success: false,
-                message: "Session ID not found".to_string(),
-            });
-        }
-    };
-
-    let session_result = sqlx::query!(
-        "SELECT u.user_name FROM Sessions_ s
-         JOIN Users_ u ON s.user_id = u.user_id
-         WHERE s.session_id = ? AND s.expires_at > NOW()",
-        session_id
-    )
-    .fetch_one(pool.get_ref())
-    .await;
-
-    let current_user_name = match session_result {
-        Ok(session) => session.user_name,
-        Err(_) => {
-            info!("Invalid or expired session ID: {}", session_id);
-            return HttpResponse::BadRequest().json(UpdateGroupResponse {
+    // Check that the current user belongs to the group
+    let membership = match require_group_member(pool.get_ref(), &current_user, owner_user_name, group_name).await {
+        Ok(membership) => membership,
+        Err(e) => {
+            info!("Group access denied for {} on {}: {}", current_user.user_name, group_name, e);
+            return HttpResponse::build(e.status_code()).json(UpdateGroupResponse {
                 success: false,
-                message: "Invalid or expired session ID".to_string(),
+                message: e.to_string(),
             });
         }
     };
+    let group_id = membership.group_id;
 
-    // Assert owner_user_name == current user name
-    if owner_user_name != &current_user_name {
-        return HttpResponse::BadRequest().json(UpdateGroupResponse {
+    // Check that the role of the member allows managing the group
+    if !membership.can(Permission::ManageGroup) {
+        info!("{} ({}) is not allowed to manage the group of {}", current_user.user_name, membership.role.as_str(), group_name);
+        return HttpResponse::Forbidden().json(UpdateGroupResponse {
             success: false,
-            message: "Unauthorized action".to_string(),
+            message: "Your role in this group does not allow this action".to_string(),
         });
     }
 
-    // Get the group_id with group_name from Groups_
-    let group_id_result = sqlx::query!(
-        "
-        SELECT g.group_id 
-        FROM Groups_ g
-        JOIN Users_ u ON g.owner_user_id = u.user_id
-        WHERE g.group_name = ? AND u.user_name = ?
-        ",
-        group_name, owner_user_name
-    )
-    .fetch_one(pool.get_ref())
-    .await;
-
-    let group_id = match group_id_result {
-        Ok(record) => record.group_id,
-        Err(_) => {
-            info!("Group not found: {}", group_name);
-            return HttpResponse::BadRequest().json(UpdateGroupResponse {
-                success: false,
-                message: "Group not found".to_string(),
-            });
+    let new_conflict_mode = if new_conflict_mode.is_empty() {
+        None
+    } else {
+        match ConflictMode::parse(new_conflict_mode) {
+            Some(mode) => Some(mode),
+            None => {
+                return HttpResponse::BadRequest().json(UpdateGroupResponse {
+                    success: false,
+                    message: "Conflict mode must be warning or strict".to_string(),
+                });
+            }
         }
     };
 
-    // Check if new_group_name is empty
-    if new_group_name.is_empty() {
-        info!("New group name is empty, maintaining the current group name for group_id: {}", group_id);
+    // Check if new_group_name and new_conflict_mode are empty
+    if new_group_name.is_empty() && new_conflict_mode.is_none() {
+        info!("Nothing to update, maintaining the current group for group_id: {}", group_id);
         return HttpResponse::Ok().json(UpdateGroupResponse {
             success: true,
             message: "Group name maintained successfully".to_string(),
@@ -255,101 +193,81 @@ pub async fn update_group(
     }
 
     // Update the group name
-    let update_result = sqlx::query!(
-        "UPDATE Groups_ SET group_name = ? WHERE group_id = ?",
-        new_group_name, group_id
-    )
-    .execute(pool.get_ref())
-    .await;
+    if !new_group_name.is_empty() {
+        let update_result = sqlx::query!(
+            "UPDATE Groups_ SET group_name = ? WHERE group_id = ?",
+            new_group_name, group_id
+        )
+        .execute(pool.get_ref())
+        .await;
+
+        if let Err(e) = update_result {
+            error!("Failed to update group name for group_id {}: {}", group_id, e);
+            return HttpResponse::InternalServerError().json(UpdateGroupResponse {
+                success: false,
+                message: "Failed to update group name".to_string(),
+            });
+        }
 
-    if let Err(e) = update_result {
-        error!("Failed to update group name for group_id {}: {}", group_id, e);
-        return HttpResponse::InternalServerError().json(UpdateGroupResponse {
-            success: false,
-            message: "Failed to update group name".to_string(),
-        });
+        info!("Group name updated successfully for group_id: {}", group_id);
     }
 
-    info!("Group name updated successfully for group_id: {}", group_id);
+    // Update the conflict mode
+    if let Some(mode) = new_conflict_mode {
+        let update_result = sqlx::query!(
+            "UPDATE Groups_ SET conflict_mode = ? WHERE group_id = ?",
+            mode.as_str(), group_id
+        )
+        .execute(pool.get_ref())
+        .await;
+
+        if let Err(e) = update_result {
+            error!("Failed to update conflict mode for group_id {}: {}", group_id, e);
+            return HttpResponse::InternalServerError().json(UpdateGroupResponse {
+                success: false,
+                message: "Failed to update conflict mode".to_string(),
+            });
+        }
+
+        info!("Conflict mode set to {} for group_id: {}", mode.as_str(), group_id);
+    }
 
     return HttpResponse::Ok().json(UpdateGroupResponse {
         success: true,
-        message: "Group name updated successfully".to_string(),
+        message: "Group updated successfully".to_string(),
     });
 }
 
 pub async fn delete_group(
     pool: web::Data<MySqlPool>,
-    req: HttpRequest,
+    current_user: CurrentUser,
     request: web::Json<DeleteGroupRequest>
 ) -> impl Responder {
     let owner_user_name = &request.owner_user_name;
     let group_name = &request.group_name;
 
-    // Get the current user name using session ID in the cookie
-    let session_id = match req.cookie("session_id") {
-        Some(cookie) => cookie.value().to_string(),
-        None => {
-            info!("Session ID not found in cookies for delete_tag");
-            return HttpResponse::BadRequest().json(DeleteGroupResponse {
-                success: false,
-                message: "Session ID not found".to_string(),
-            });
-        }
-    };
-
-    let session_result = sqlx::query!(
-        "SELECT u.user_name FROM Sessions_ s
-         JOIN Users_ u ON s.user_id = u.user_id
-         WHERE s.session_id = ? AND s.expires_at > NOW()",
-        session_id
-    )
-    .fetch_one(pool.get_ref())
-    .await;
-
-    let current_user_name = match session_result {
-        Ok(session) => session.user_name,
-        Err(_) => {
-            info!("Invalid or expired session ID: {}", session_id);
-            return HttpResponse::BadRequest().json(DeleteGroupResponse {
+    // Check that the current user belongs to the group
+    let membership = match require_group_member(pool.get_ref(), &current_user, owner_user_name, group_name).await {
+        Ok(membership) => membership,
+        Err(e) => {
+            info!("Group access denied for {} on {}: {}", current_user.user_name, group_name, e);
+            return HttpResponse::build(e.status_code()).json(DeleteGroupResponse {
                 success: false,
-                message: "Invalid or expired session ID".to_string(),
+                message: e.to_string(),
             });
         }
     };
+    let group_id = membership.group_id;
 
-    // Assert owner_user_name == current user name
-    if owner_user_name != &current_user_name {
-        return HttpResponse::BadRequest().json(UpdateGroupResponse {
+    // Check that the role of the member allows managing the group
+    if !membership.can(Permission::ManageGroup) {
+        info!("{} ({}) is not allowed to manage the group of {}", current_user.user_name, membership.role.as_str(), group_name);
+        return HttpResponse::Forbidden().json(DeleteGroupResponse {
             success: false,
-            message: "Unauthorized action".to_string(),
+            message: "Your role in this group does not allow this action".to_string(),
         });
     }
 
-    // Get the group_id with group_name from Groups_
-    let group_id_result = sqlx::query!(
-        "
-        SELECT g.group_id 
-        FROM Groups_ g
-        JOIN Users_ u ON g.owner_user_id = u.user_id
-        WHERE g.group_name = ? AND u.user_name = ?
-        ",
-        group_name, owner_user_name
-    )
-    .fetch_one(pool.get_ref())
-    .await;
-
-    let group_id = match group_id_result {
-        Ok(record) => record.group_id,
-        Err(_) => {
-            info!("Group not found: {}", group_name);
-            return HttpResponse::BadRequest().json(DeleteGroupResponse {
-                success: false,
-                message: "Group not found".to_string(),
-            });
-        }
-    };
-
 
     // Start a transaction
     let mut tx = match pool.begin().await {
@@ -363,6 +281,45 @@ pub async fn delete_group(
         }
     };
 
+    // Note the attachment files of the group, whose rows go with it
+    let storage_names: Vec<String> = match sqlx::query_scalar!(
+        "SELECT storage_name FROM Attachments_ WHERE group_id = ?",
+        group_id
+    )
+    .fetch_all(&mut *tx)
+    .await
+    {
+        Ok(storage_names) => storage_names,
+        Err(e) => {
+            error!("Failed to fetch attachments for group {}: {}", group_id, e);
+            tx.rollback().await.unwrap();
+            return HttpResponse::InternalServerError().json(DeleteGroupResponse {
+                success: false,
+                message: "Failed to fetch attachments".to_string(),
+            });
+        }
+    };
+
+    // Delete dependencies between tasks of the group
+    let delete_dependencies_result = sqlx::query!(
+        "DELETE d FROM Dependencies_ d
+         JOIN Tasks_ t ON d.prev_task_id = t.task_id OR d.next_task_id = t.task_id
+         JOIN Projects_ p ON t.project_id = p.project_id
+         WHERE p.group_id = ?",
+        group_id
+    )
+    .execute(&mut *tx)
+    .await;
+
+    if let Err(e) = delete_dependencies_result {
+        error!("Failed to delete dependencies for group {}: {}", group_id, e);
+        tx.rollback().await.unwrap();
+        return HttpResponse::InternalServerError().json(DeleteGroupResponse {
+            success: false,
+            message: "Failed to delete dependencies".to_string(),
+        });
+    }
+
     // Delete tasks associated with projects in the group
     let delete_tasks_result = sqlx::query!(
         "DELETE t FROM Tasks_ t
@@ -475,9 +432,542 @@ pub async fn delete_group(
         });
     }
 
+    remove_attachment_files(&storage_names).await;
+
     info!("Group {} deleted successfully", group_name);
     return HttpResponse::Ok().json(DeleteGroupResponse {
         success: true,
         message: "Group deleted successfully".to_string(),
     });
+}
+
+// Handler to hand the group over to another member, the previous owner stays on as an admin
+pub async fn transfer_ownership(
+    pool: web::Data<MySqlPool>,
+    current_user: CurrentUser,
+    request: web::Json<TransferOwnershipRequest>,
+) -> impl Responder {
+    let owner_user_name = &request.owner_user_name;
+    let group_name = &request.group_name;
+    let new_owner_user_name = &request.new_owner_user_name;
+
+    // Check that the current user belongs to the group
+    let membership = match require_group_member(pool.get_ref(), &current_user, owner_user_name, group_name).await {
+        Ok(membership) => membership,
+        Err(e) => {
+            info!("Group access denied for {} on {}: {}", current_user.user_name, group_name, e);
+            return HttpResponse::build(e.status_code()).json(TransferOwnershipResponse {
+                success: false,
+                message: e.to_string(),
+            });
+        }
+    };
+    let group_id = membership.group_id;
+
+    // Check that the role of the member allows managing the group
+    if !membership.can(Permission::ManageGroup) {
+        info!("{} ({}) is not allowed to manage the group of {}", current_user.user_name, membership.role.as_str(), group_name);
+        return HttpResponse::Forbidden().json(TransferOwnershipResponse {
+            success: false,
+            message: "Your role in this group does not allow this action".to_string(),
+        });
+    }
+
+    // The new owner has to be a member of the group already
+    let new_owner_result = sqlx::query!(
+        "SELECT u.user_id
+         FROM GroupUserMapping_ gum
+         JOIN Users_ u ON gum.user_id = u.user_id
+         WHERE gum.group_id = ? AND u.user_name = ?",
+        group_id, new_owner_user_name
+    )
+    .fetch_optional(pool.get_ref())
+    .await;
+
+    let new_owner_user_id = match new_owner_result {
+        Ok(Some(record)) if record.user_id != membership.owner_user_id => record.user_id,
+        Ok(Some(_)) => {
+            return HttpResponse::BadRequest().json(TransferOwnershipResponse {
+                success: false,
+                message: "User already owns the group".to_string(),
+            });
+        }
+        Ok(None) => {
+            info!("{} is not a member of group {}", new_owner_user_name, group_id);
+            return HttpResponse::BadRequest().json(TransferOwnershipResponse {
+                success: false,
+                message: "New owner is not a member of this group".to_string(),
+            });
+        }
+        Err(e) => {
+            error!("Failed to fetch member {} of group {}: {}", new_owner_user_name, group_id, e);
+            return HttpResponse::InternalServerError().json(TransferOwnershipResponse {
+                success: false,
+                message: "Failed to fetch new owner".to_string(),
+            });
+        }
+    };
+
+    // Group names are unique per owner
+    let name_taken_result = sqlx::query!(
+        "SELECT COUNT(*) AS count FROM Groups_ WHERE owner_user_id = ? AND group_name = ?",
+        new_owner_user_id, group_name
+    )
+    .fetch_one(pool.get_ref())
+    .await;
+
+    match name_taken_result {
+        Ok(record) if record.count > 0 => {
+            info!("{} already owns a group named {}", new_owner_user_name, group_name);
+            return HttpResponse::BadRequest().json(TransferOwnershipResponse {
+                success: false,
+                message: "New owner already owns a group with the same name".to_string(),
+            });
+        }
+        Ok(_) => {}
+        Err(e) => {
+            error!("Failed to check group names of {}: {}", new_owner_user_name, e);
+            return HttpResponse::InternalServerError().json(TransferOwnershipResponse {
+                success: false,
+                message: "Failed to check group name".to_string(),
+            });
+        }
+    }
+
+    // Start a transaction
+    let mut tx = match pool.begin().await {
+        Ok(transaction) => transaction,
+        Err(e) => {
+            error!("Failed to start a transaction: {}", e);
+            return HttpResponse::InternalServerError().json(TransferOwnershipResponse {
+                success: false,
+                message: "Failed to start a transaction".to_string(),
+            });
+        }
+    };
+
+    let update_group_result = sqlx::query!(
+        "UPDATE Groups_ SET owner_user_id = ? WHERE group_id = ?",
+        new_owner_user_id, group_id
+    )
+    .execute(&mut *tx)
+    .await;
+
+    if let Err(e) = update_group_result {
+        error!("Failed to change owner of group {}: {}", group_id, e);
+        let _ = tx.rollback().await;
+        return HttpResponse::InternalServerError().json(TransferOwnershipResponse {
+            success: false,
+            message: "Failed to transfer ownership".to_string(),
+        });
+    }
+
+    // The new owner takes the owner role, the previous owner stays on as an admin
+    let update_roles_result = sqlx::query!(
+        "UPDATE GroupUserMapping_
+         SET role = CASE WHEN user_id = ? THEN 'owner' ELSE 'admin' END
+         WHERE group_id = ? AND user_id IN (?, ?)",
+        new_owner_user_id, group_id, new_owner_user_id, membership.owner_user_id
+    )
+    .execute(&mut *tx)
+    .await;
+
+    if let Err(e) = update_roles_result {
+        error!("Failed to update member roles of group {}: {}", group_id, e);
+        let _ = tx.rollback().await;
+        return HttpResponse::InternalServerError().json(TransferOwnershipResponse {
+            success: false,
+            message: "Failed to update member roles".to_string(),
+        });
+    }
+
+    if let Err(e) = tx.commit().await {
+        error!("Failed to commit transaction: {}", e);
+        return HttpResponse::InternalServerError().json(TransferOwnershipResponse {
+            success: false,
+            message: "Failed to commit transaction".to_string(),
+        });
+    }
+
+    info!("Ownership of group {} transferred from {} to {}", group_id, current_user.user_name, new_owner_user_name);
+    HttpResponse::Ok().json(TransferOwnershipResponse {
+        success: true,
+        message: "Ownership transferred successfully".to_string(),
+    })
+}
+
+// Handler to get the pending invitations of the current user
+pub async fn get_invitation_list(
+    pool: web::Data<MySqlPool>,
+    current_user: CurrentUser,
+    _: web::Json<GetInvitationListRequest>,
+) -> impl Responder {
+    let user_id = current_user.user_id;
+
+    let invitations_result = sqlx::query!(
+        "SELECT gi.invitation_id, g.group_name, o.user_name AS owner_username, i.user_name AS inviter_username,
+                gi.role, gi.created_at, gi.expires_at
+         FROM GroupInvitations_ gi
+         JOIN Groups_ g ON gi.group_id = g.group_id
+         JOIN Users_ o ON g.owner_user_id = o.user_id
+         JOIN Users_ i ON gi.inviter_user_id = i.user_id
+         WHERE gi.invitee_user_id = ? AND gi.status = 'pending' AND gi.expires_at > NOW()
+         ORDER BY gi.created_at",
+        user_id
+    )
+    .fetch_all(pool.get_ref())
+    .await;
+
+    match invitations_result {
+        Ok(records) => {
+            let invitations: Vec<Invitation> = records.into_iter().map(|record| Invitation {
+                invitation_id: record.invitation_id,
+                group_name: record.group_name,
+                owner_username: record.owner_username,
+                inviter_username: record.inviter_username,
+                role: record.role,
+                created_at: record.created_at.to_string(),
+                expires_at: record.expires_at.to_string(),
+            }).collect();
+
+            HttpResponse::Ok().json(GetInvitationListResponse { invitations })
+        }
+        Err(e) => {
+            error!("Failed to fetch invitations for user_id {}: {}", user_id, e);
+            HttpResponse::InternalServerError().json(GetInvitationListResponse { invitations: Vec::new() })
+        }
+    }
+}
+
+// Handler to accept a pending invitation and join the group
+pub async fn accept_invitation(
+    pool: web::Data<MySqlPool>,
+    current_user: CurrentUser,
+    request: web::Json<AcceptInvitationRequest>,
+) -> impl Responder {
+    let invitation_id = request.invitation_id;
+    let user_id = current_user.user_id;
+
+    // Start a transaction
+    let mut tx = match pool.begin().await {
+        Ok(transaction) => transaction,
+        Err(e) => {
+            error!("Failed to start a transaction: {}", e);
+            return HttpResponse::InternalServerError().json(AcceptInvitationResponse {
+                success: false,
+                message: "Failed to start a transaction".to_string(),
+            });
+        }
+    };
+
+    let invitation_result = sqlx::query!(
+        "SELECT group_id, role FROM GroupInvitations_
+         WHERE invitation_id = ? AND invitee_user_id = ? AND status = 'pending' AND expires_at > NOW()
+         FOR UPDATE",
+        invitation_id, user_id
+    )
+    .fetch_optional(&mut *tx)
+    .await;
+
+    let invitation = match invitation_result {
+        Ok(Some(record)) => record,
+        Ok(None) => {
+            info!("Invitation {} not found or expired for user_id {}", invitation_id, user_id);
+            let _ = tx.rollback().await;
+            return HttpResponse::BadRequest().json(AcceptInvitationResponse {
+                success: false,
+                message: "Invitation not found or expired".to_string(),
+            });
+        }
+        Err(e) => {
+            error!("Failed to fetch invitation {}: {}", invitation_id, e);
+            let _ = tx.rollback().await;
+            return HttpResponse::InternalServerError().json(AcceptInvitationResponse {
+                success: false,
+                message: "Failed to fetch invitation".to_string(),
+            });
+        }
+    };
+
+    // Members keep their current role if they joined in the meantime
+    let insert_result = sqlx::query!(
+        "INSERT IGNORE INTO GroupUserMapping_ (group_id, user_id, role) VALUES (?, ?, ?)",
+        invitation.group_id, user_id, invitation.role
+    )
+    .execute(&mut *tx)
+    .await;
+
+    if let Err(e) = insert_result {
+        error!("Failed to add user_id {} to group {}: {}", user_id, invitation.group_id, e);
+        let _ = tx.rollback().await;
+        return HttpResponse::InternalServerError().json(AcceptInvitationResponse {
+            success: false,
+            message: "Failed to join group".to_string(),
+        });
+    }
+
+    let update_result = sqlx::query!(
+        "UPDATE GroupInvitations_ SET status = 'accepted' WHERE invitation_id = ?",
+        invitation_id
+    )
+    .execute(&mut *tx)
+    .await;
+
+    if let Err(e) = update_result {
+        error!("Failed to accept invitation {}: {}", invitation_id, e);
+        let _ = tx.rollback().await;
+        return HttpResponse::InternalServerError().json(AcceptInvitationResponse {
+            success: false,
+            message: "Failed to accept invitation".to_string(),
+        });
+    }
+
+    if let Err(e) = tx.commit().await {
+        error!("Failed to commit transaction: {}", e);
+        return HttpResponse::InternalServerError().json(AcceptInvitationResponse {
+            success: false,
+            message: "Failed to commit transaction".to_string(),
+        });
+    }
+
+    info!("{} joined group {} through invitation {}", current_user.user_name, invitation.group_id, invitation_id);
+    HttpResponse::Ok().json(AcceptInvitationResponse {
+        success: true,
+        message: "Invitation accepted successfully".to_string(),
+    })
+}
+
+// Handler to decline a pending invitation
+pub async fn decline_invitation(
+    pool: web::Data<MySqlPool>,
+    current_user: CurrentUser,
+    request: web::Json<DeclineInvitationRequest>,
+) -> impl Responder {
+    let invitation_id = request.invitation_id;
+    let user_id = current_user.user_id;
+
+    let update_result = sqlx::query!(
+        "UPDATE GroupInvitations_ SET status = 'declined'
+         WHERE invitation_id = ? AND invitee_user_id = ? AND status = 'pending' AND expires_at > NOW()",
+        invitation_id, user_id
+    )
+    .execute(pool.get_ref())
+    .await;
+
+    match update_result {
+        Ok(result) if result.rows_affected() == 0 => {
+            info!("Invitation {} not found or expired for user_id {}", invitation_id, user_id);
+            HttpResponse::BadRequest().json(DeclineInvitationResponse {
+                success: false,
+                message: "Invitation not found or expired".to_string(),
+            })
+        }
+        Ok(_) => {
+            info!("{} declined invitation {}", current_user.user_name, invitation_id);
+            HttpResponse::Ok().json(DeclineInvitationResponse {
+                success: true,
+                message: "Invitation declined successfully".to_string(),
+            })
+        }
+        Err(e) => {
+            error!("Failed to decline invitation {}: {}", invitation_id, e);
+            HttpResponse::InternalServerError().json(DeclineInvitationResponse {
+                success: false,
+                message: "Failed to decline invitation".to_string(),
+            })
+        }
+    }
+}
+
+// Handler to join a group through an invite link
+pub async fn join_group(
+    pool: web::Data<MySqlPool>,
+    current_user: CurrentUser,
+    request: web::Json<JoinGroupRequest>,
+) -> impl Responder {
+    let token = &request.token;
+    let user_id = current_user.user_id;
+
+    // Start a transaction
+    let mut tx = match pool.begin().await {
+        Ok(transaction) => transaction,
+        Err(e) => {
+            error!("Failed to start a transaction: {}", e);
+            return HttpResponse::InternalServerError().json(JoinGroupResponse {
+                success: false,
+                message: "Failed to start a transaction".to_string(),
+                group_name: String::new(),
+                owner_username: String::new(),
+            });
+        }
+    };
+
+    // Lock the link so concurrent joins cannot exceed max_uses
+    let link_result = sqlx::query!(
+        "SELECT l.link_id, l.group_id, l.role, g.group_name, u.user_name AS owner_username
+         FROM GroupInviteLinks_ l
+         JOIN Groups_ g ON l.group_id = g.group_id
+         JOIN Users_ u ON g.owner_user_id = u.user_id
+         WHERE l.token = ?
+           AND (l.expires_at IS NULL OR l.expires_at > NOW())
+           AND (l.max_uses IS NULL OR l.use_count < l.max_uses)
+         FOR UPDATE",
+        token
+    )
+    .fetch_optional(&mut *tx)
+    .await;
+
+    let link = match link_result {
+        Ok(Some(record)) => record,
+        Ok(None) => {
+            info!("Invalid or used up invite link for user_id {}", user_id);
+            let _ = tx.rollback().await;
+            return HttpResponse::BadRequest().json(JoinGroupResponse {
+                success: false,
+                message: "Invite link is invalid, expired or used up".to_string(),
+                group_name: String::new(),
+                owner_username: String::new(),
+            });
+        }
+        Err(e) => {
+            error!("Failed to fetch invite link: {}", e);
+            let _ = tx.rollback().await;
+            return HttpResponse::InternalServerError().json(JoinGroupResponse {
+                success: false,
+                message: "Failed to fetch invite link".to_string(),
+                group_name: String::new(),
+                owner_username: String::new(),
+            });
+        }
+    };
+
+    let insert_result = sqlx::query!(
+        "INSERT IGNORE INTO GroupUserMapping_ (group_id, user_id, role) VALUES (?, ?, ?)",
+        link.group_id, user_id, link.role
+    )
+    .execute(&mut *tx)
+    .await;
+
+    match insert_result {
+        Ok(result) if result.rows_affected() == 0 => {
+            let _ = tx.rollback().await;
+            return HttpResponse::BadRequest().json(JoinGroupResponse {
+                success: false,
+                message: "Already a member of this group".to_string(),
+                group_name: link.group_name,
+                owner_username: link.owner_username,
+            });
+        }
+        Ok(_) => {}
+        Err(e) => {
+            error!("Failed to add user_id {} to group {}: {}", user_id, link.group_id, e);
+            let _ = tx.rollback().await;
+            return HttpResponse::InternalServerError().json(JoinGroupResponse {
+                success: false,
+                message: "Failed to join group".to_string(),
+                group_name: String::new(),
+                owner_username: String::new(),
+            });
+        }
+    }
+
+    let update_result = sqlx::query!(
+        "UPDATE GroupInviteLinks_ SET use_count = use_count + 1 WHERE link_id = ?",
+        link.link_id
+    )
+    .execute(&mut *tx)
+    .await;
+
+    if let Err(e) = update_result {
+        error!("Failed to count use of invite link {}: {}", link.link_id, e);
+        let _ = tx.rollback().await;
+        return HttpResponse::InternalServerError().json(JoinGroupResponse {
+            success: false,
+            message: "Failed to join group".to_string(),
+            group_name: String::new(),
+            owner_username: String::new(),
+        });
+    }
+
+    if let Err(e) = tx.commit().await {
+        error!("Failed to commit transaction: {}", e);
+        return HttpResponse::InternalServerError().json(JoinGroupResponse {
+            success: false,
+            message: "Failed to commit transaction".to_string(),
+            group_name: String::new(),
+            owner_username: String::new(),
+        });
+    }
+
+    info!("{} joined group {} through invite link {}", current_user.user_name, link.group_id, link.link_id);
+    HttpResponse::Ok().json(JoinGroupResponse {
+        success: true,
+        message: "Joined group successfully".to_string(),
+        group_name: link.group_name,
+        owner_username: link.owner_username,
+    })
+}
+
+// Handler to get the tasks assigned to the current user across all of their groups
+pub async fn get_my_task_list(
+    pool: web::Data<MySqlPool>,
+    current_user: CurrentUser,
+    _: web::Json<GetMyTaskListRequest>,
+) -> impl Responder {
+    let user_id = current_user.user_id;
+
+    let tasks_result = sqlx::query!(
+        "SELECT g.group_name,
+                o.user_name AS owner_username,
+                p.project_name,
+                t.title AS task_title,
+                t.start_time,
+                t.end_time,
+                t.description,
+                t.status,
+                t.progress,
+                (SELECT GROUP_CONCAT(au.user_name ORDER BY au.user_name SEPARATOR ',')
+                 FROM TaskAssignees_ tas2
+                 JOIN Users_ au ON tas2.user_id = au.user_id
+                 WHERE tas2.task_id = t.task_id) AS worker_names,
+                GROUP_CONCAT(DISTINCT ta.tag_color SEPARATOR ',') AS tag_colors
+         FROM Tasks_ t
+         JOIN Projects_ p ON t.project_id = p.project_id
+         JOIN Groups_ g ON p.group_id = g.group_id
+         JOIN Users_ o ON g.owner_user_id = o.user_id
+         JOIN TaskAssignees_ tas ON tas.task_id = t.task_id
+         JOIN GroupUserMapping_ gum ON gum.group_id = g.group_id AND gum.user_id = tas.user_id
+         LEFT JOIN TagProjectMapping_ tpm ON p.project_id = tpm.project_id
+         LEFT JOIN Tags_ ta ON tpm.tag_id = ta.tag_id
+         WHERE tas.user_id = ?
+         GROUP BY t.task_id
+         ORDER BY t.start_time",
+        user_id
+    )
+    .fetch_all(pool.get_ref())
+    .await;
+
+    match tasks_result {
+        Ok(records) => {
+            let tasks: Vec<MyTask> = records.into_iter().map(|record| MyTask {
+                group_name: record.group_name,
+                owner_username: record.owner_username,
+                project_name: record.project_name,
+                task_title: record.task_title,
+                start_time: record.start_time.to_string(),
+                end_time: record.end_time.to_string(),
+                description: record.description,
+                status: record.status,
+                progress: record.progress,
+                worker_names: record.worker_names.map(|names| names.split(',').map(|s| s.to_string()).collect()).unwrap_or_default(),
+                tag_colors: record.tag_colors.map(|colors| colors.split(',').map(|s| s.to_string()).collect()).unwrap_or_default(),
+            }).collect();
+
+            HttpResponse::Ok().json(GetMyTaskListResponse { tasks })
+        }
+        Err(e) => {
+            error!("Failed to fetch tasks of user_id {}: {}", user_id, e);
+            HttpResponse::InternalServerError().json(GetMyTaskListResponse { tasks: Vec::new() })
+        }
+    }
 }
\ No newline at end of file
@@ -9,7 +9,10 @@ pub struct GetGroupListRequest {
 pub struct Group {
     pub group_name: String,
     pub writeable: bool,
+    // owner, admin, editor or viewer
+    pub role: String,
     pub owner_username: String,
+    pub conflict_mode: String,
 }
 
 // list of groups
@@ -35,6 +38,9 @@ pub struct UpdateGroupRequest {
     pub owner_user_name: String,
     pub group_name: String,
     pub new_group_name: String,
+    // warning or strict; empty keeps the current mode
+    #[serde(default)]
+    pub new_conflict_mode: String,
 }
 // list of groups
 #[derive(Serialize)]
@@ -53,4 +59,101 @@ pub struct DeleteGroupRequest {
 pub struct DeleteGroupResponse {
     pub success: bool,
     pub message: String,
+}
+
+#[derive(Deserialize)]
+pub struct TransferOwnershipRequest {
+    pub owner_user_name: String,
+    pub group_name: String,
+    pub new_owner_user_name: String,
+}
+
+#[derive(Serialize)]
+pub struct TransferOwnershipResponse {
+    pub success: bool,
+    pub message: String,
+}
+
+#[derive(Deserialize)]
+pub struct GetInvitationListRequest {
+    // if needed
+}
+
+#[derive(Serialize)]
+pub struct Invitation {
+    pub invitation_id: i32,
+    pub group_name: String,
+    pub owner_username: String,
+    pub inviter_username: String,
+    pub role: String,
+    pub created_at: String,
+    pub expires_at: String,
+}
+
+// pending invitations of the current user
+#[derive(Serialize)]
+pub struct GetInvitationListResponse {
+    pub invitations: Vec<Invitation>,
+}
+
+#[derive(Deserialize)]
+pub struct AcceptInvitationRequest {
+    pub invitation_id: i32,
+}
+
+#[derive(Serialize)]
+pub struct AcceptInvitationResponse {
+    pub success: bool,
+    pub message: String,
+}
+
+#[derive(Deserialize)]
+pub struct DeclineInvitationRequest {
+    pub invitation_id: i32,
+}
+
+#[derive(Serialize)]
+pub struct DeclineInvitationResponse {
+    pub success: bool,
+    pub message: String,
+}
+
+#[derive(Deserialize)]
+pub struct JoinGroupRequest {
+    pub token: String,
+}
+
+#[derive(Serialize)]
+pub struct JoinGroupResponse {
+    pub success: bool,
+    pub message: String,
+    pub group_name: String,
+    pub owner_username: String,
+}
+
+#[derive(Deserialize)]
+pub struct GetMyTaskListRequest {
+    // if needed
+}
+
+#[derive(Serialize)]
+pub struct MyTask {
+    pub group_name: String,
+    pub owner_username: String,
+    pub project_name: String,
+    pub task_title: String,
+    pub start_time: String,
+    pub end_time: String,
+    pub description: String,
+    pub status: String,
+    pub progress: i32,
+    // every assignee of the task, including the current user
+    pub worker_names: Vec<String>,
+    pub tag_colors: Vec<String>,
+}
+
+// tasks assigned to the current user in every group they belong to
+#[derive(Serialize)]
+pub struct GetMyTaskListResponse {
+    pub tasks: Vec<MyTask>,
 }
\ No newline at end of file
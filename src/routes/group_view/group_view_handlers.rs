@@ -1,225 +1,1046 @@
-use actix_web::{web, HttpResponse, HttpRequest, Responder};
+use actix_web::{web, HttpResponse, Responder};
 use sqlx::{MySqlPool, Row};
-use time::PrimitiveDateTime;
+use std::collections::HashMap;
+use time::{PrimitiveDateTime, macros::format_description};
 use log::{error, info};
+use uuid::Uuid;
+use chrono::{Utc, Duration};
 // use time::PrimitiveDateTime;
+use crate::models::group_user_mapping::{GroupRole, Permission};
+use crate::models::notification::NotificationKind;
+use crate::routes::notification::notification_helpers::notify_users;
+use crate::routes::auth::auth_extractors::{CurrentUser, require_group_member};
+use super::group_view_workload::{compute_workload, hours};
+use super::group_view_workflow::{fetch_workflow, Workflow};
 use super::group_view_models::{
     GetWorkerListRequest, GetWorkerListResponse, Worker,
     AddWorkerRequest, AddWorkerResponse,
+    RemoveWorkerRequest, RemoveWorkerResponse,
+    UpdateWorkerRoleRequest, UpdateWorkerRoleResponse,
+    LeaveGroupRequest, LeaveGroupResponse,
+    AddInviteLinkRequest, AddInviteLinkResponse,
+    GetInviteLinkListRequest, GetInviteLinkListResponse, InviteLink,
+    DeleteInviteLinkRequest, DeleteInviteLinkResponse,
+    GetWorkflowRequest, GetWorkflowResponse, WorkflowTransition,
+    UpdateWorkflowRequest, UpdateWorkflowResponse,
     GetTagListRequest, GetTagListResponse, Tag,
     AddTagRequest, AddTagResponse,
     UpdateTagRequest, UpdateTagResponse,
     DeleteTagRequest, DeleteTagResponse,
     GetTaskListByTagListRequest, GetTaskListByTagListResponse, Task,
-    GetTaskListByProjectNameRequest, GetTaskListByProjectNameResponse,
-    GetProjectListRequest, GetProjectListResponse, Project
+    GetTaskListByProjectNameRequest, GetTaskListByProjectNameResponse, Dependency,
+    GetProjectListRequest, GetProjectListResponse, Project,
+    GetWorkloadReportRequest, GetWorkloadReportResponse, WorkerWorkload, Period,
 };
 
+// Longest lifetime of an invite link
+const MAX_INVITE_LINK_HOURS: i64 = 24 * 365;
+
 // Default handler for group selection root
 pub async fn group_view_get() -> impl Responder {
     HttpResponse::Ok().body("Hello, this is the Group View endpoint.")
 }
 
-// Handler to get the worker list
-pub async fn get_worker_list(
+// Handler to get the worker list
+pub async fn get_worker_list(
+    pool: web::Data<MySqlPool>,
+    current_user: CurrentUser,
+    request: web::Json<GetWorkerListRequest>,
+) -> impl Responder {
+    let owner_user_name = &request.owner_user_name;
+    let group_name = &request.group_name;
+    
+    // Check that the current user belongs to the group
+    let group_id = match require_group_member(pool.get_ref(), &current_user, owner_user_name, group_name).await {
+        Ok(membership) => membership.group_id,
+        Err(e) => {
+            info!("Group access denied for {} on {}: {}", current_user.user_name, group_name, e);
+            return HttpResponse::build(e.status_code()).json(GetWorkerListResponse { workers: Vec::new() });
+        }
+    };
+
+    // Get the worker list from GroupUserMapping_ where group_id is the one from the previous query
+    let workers_result = sqlx::query!(
+        "SELECT u.user_name, u.user_email, gum.role 
+         FROM GroupUserMapping_ gum
+         JOIN Users_ u ON gum.user_id = u.user_id
+         WHERE gum.group_id = ? AND u.user_name != ?",
+        group_id, owner_user_name
+    )
+    .fetch_all(pool.get_ref())
+    .await;
+
+    match workers_result {
+        Ok(records) => {
+            let workers: Vec<Worker> = records.into_iter().map(|record| Worker {
+                user_name: record.user_name,
+                user_email: record.user_email,
+                role: record.role,
+            }).collect();
+
+            HttpResponse::Ok().json(GetWorkerListResponse { workers })
+        },
+        Err(e) => {
+            error!("Failed to fetch workers for group_id {}: {}", group_id, e);
+            HttpResponse::InternalServerError().json(GetWorkerListResponse { workers: Vec::new() })
+        }
+    }
+}
+
+// Handler to invite a worker to the group
+pub async fn add_worker(
+    pool: web::Data<MySqlPool>,
+    current_user: CurrentUser,
+    request: web::Json<AddWorkerRequest>,
+) -> impl Responder {
+    let owner_user_name = &request.owner_user_name;
+    let group_name = &request.group_name;
+    let worker_user_name = &request.worker_user_name;
+    let worker_user_email = &request.worker_user_email;
+    let role = match request.role {
+        Some(role) => role,
+        None if request.writeable => GroupRole::Editor,
+        None => GroupRole::Viewer,
+    };
+
+    // Check that the current user belongs to the group
+    let membership = match require_group_member(pool.get_ref(), &current_user, owner_user_name, group_name).await {
+        Ok(membership) => membership,
+        Err(e) => {
+            info!("Group access denied for {} on {}: {}", current_user.user_name, group_name, e);
+            return HttpResponse::build(e.status_code()).json(AddWorkerResponse {
+                success: false,
+                message: e.to_string(),
+            });
+        }
+    };
+    let group_id = membership.group_id;
+
+    // Check that the role of the member allows managing members
+    if !membership.can(Permission::ManageMembers) {
+        info!("{} ({}) is not allowed to manage members of {}", current_user.user_name, membership.role.as_str(), group_name);
+        return HttpResponse::Forbidden().json(AddWorkerResponse {
+            success: false,
+            message: "Your role in this group does not allow this action".to_string(),
+        });
+    }
+
+    // A group has a single owner and only the owner can appoint admins
+    if role == GroupRole::Owner {
+        return HttpResponse::BadRequest().json(AddWorkerResponse {
+            success: false,
+            message: "Use transfer of ownership to make a member the owner".to_string(),
+        });
+    }
+    if role == GroupRole::Admin && membership.role != GroupRole::Owner {
+        return HttpResponse::Forbidden().json(AddWorkerResponse {
+            success: false,
+            message: "Only the owner can add admins".to_string(),
+        });
+    }
+
+    // Look up the invited user by user name or email
+    let worker_result = if worker_user_name.is_empty() {
+        sqlx::query!(
+            "SELECT user_id, user_name FROM Users_ WHERE user_email = ?",
+            worker_user_email
+        )
+        .fetch_optional(pool.get_ref())
+        .await
+        .map(|record| record.map(|record| (record.user_id, record.user_name)))
+    } else {
+        sqlx::query!(
+            "SELECT user_id, user_name FROM Users_ WHERE user_name = ?",
+            worker_user_name
+        )
+        .fetch_optional(pool.get_ref())
+        .await
+        .map(|record| record.map(|record| (record.user_id, record.user_name)))
+    };
+
+    let (worker_user_id, worker_user_name) = match worker_result {
+        Ok(Some(worker)) => worker,
+        Ok(None) => {
+            info!("Worker not found: {}{}", worker_user_name, worker_user_email);
+            return HttpResponse::BadRequest().json(AddWorkerResponse {
+                success: false,
+                message: "Worker not found".to_string(),
+            });
+        }
+        Err(e) => {
+            error!("Failed to fetch worker {}{}: {}", worker_user_name, worker_user_email, e);
+            return HttpResponse::InternalServerError().json(AddWorkerResponse {
+                success: false,
+                message: "Failed to fetch worker".to_string(),
+            });
+        }
+    };
+
+    // Members and users with a pending invitation cannot be invited again
+    let existing_result = sqlx::query!(
+        "SELECT
+            (SELECT COUNT(*) FROM GroupUserMapping_ WHERE group_id = ? AND user_id = ?) AS member_count,
+            (SELECT COUNT(*) FROM GroupInvitations_
+             WHERE group_id = ? AND invitee_user_id = ? AND status = 'pending' AND expires_at > NOW()) AS invitation_count",
+        group_id, worker_user_id, group_id, worker_user_id
+    )
+    .fetch_one(pool.get_ref())
+    .await;
+
+    match existing_result {
+        Ok(record) if record.member_count.unwrap_or(0) > 0 => {
+            return HttpResponse::BadRequest().json(AddWorkerResponse {
+                success: false,
+                message: "Worker is already a member of this group".to_string(),
+            });
+        }
+        Ok(record) if record.invitation_count.unwrap_or(0) > 0 => {
+            return HttpResponse::BadRequest().json(AddWorkerResponse {
+                success: false,
+                message: "Worker already has a pending invitation".to_string(),
+            });
+        }
+        Ok(_) => {}
+        Err(e) => {
+            error!("Failed to check membership of {} in group {}: {}", worker_user_name, group_id, e);
+            return HttpResponse::InternalServerError().json(AddWorkerResponse {
+                success: false,
+                message: "Failed to check membership".to_string(),
+            });
+        }
+    }
+
+    // Invite the worker with the requested role, the invitation expires after a week
+    let insert_result = sqlx::query!(
+        "INSERT INTO GroupInvitations_ (group_id, invitee_user_id, inviter_user_id, role, expires_at)
+         VALUES (?, ?, ?, ?, NOW() + INTERVAL 7 DAY)",
+        group_id, worker_user_id, current_user.user_id, role.as_str()
+    )
+    .execute(pool.get_ref())
+    .await;
+
+    match insert_result {
+        Ok(_) => {
+            info!("{} invited {} to group {}", current_user.user_name, worker_user_name, group_id);
+            let message = format!("{} invited you to join group '{}' as {}", current_user.user_name, group_name, role.as_str());
+            notify_users(pool.get_ref(), &[worker_user_id], NotificationKind::GroupInvitation, &message, Some(group_id), None).await;
+            HttpResponse::Ok().json(AddWorkerResponse {
+                success: true,
+                message: "Invitation sent successfully".to_string(),
+            })
+        }
+        Err(e) => {
+            error!("Failed to invite worker to group {}: {}", group_id, e);
+            HttpResponse::InternalServerError().json(AddWorkerResponse {
+                success: false,
+                message: "Failed to invite worker".to_string(),
+            })
+        }
+    }
+}
+
+// Find the user_id and role of a member of the group by user name
+async fn find_member(
+    pool: &MySqlPool,
+    group_id: i32,
+    user_name: &str,
+) -> Result<Option<(i32, GroupRole)>, sqlx::Error> {
+    let member = sqlx::query!(
+        "SELECT u.user_id, gum.role
+         FROM GroupUserMapping_ gum
+         JOIN Users_ u ON gum.user_id = u.user_id
+         WHERE gum.group_id = ? AND u.user_name = ?",
+        group_id, user_name
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(member.map(|record| (record.user_id, GroupRole::parse(&record.role).unwrap_or(GroupRole::Viewer))))
+}
+
+// Hand the tasks of a member over to reassign_to (or unassign them) and drop the membership.
+// Returns the number of tasks that changed hands.
+async fn release_member(
+    tx: &mut sqlx::Transaction<'_, sqlx::MySql>,
+    group_id: i32,
+    user_id: i32,
+    reassign_to: Option<i32>,
+) -> Result<u64, sqlx::Error> {
+    if let Some(reassign_to) = reassign_to {
+        sqlx::query!(
+            "INSERT IGNORE INTO TaskAssignees_ (task_id, user_id)
+             SELECT tas.task_id, ?
+             FROM TaskAssignees_ tas
+             JOIN Tasks_ t ON tas.task_id = t.task_id
+             JOIN Projects_ p ON t.project_id = p.project_id
+             WHERE p.group_id = ? AND tas.user_id = ?",
+            reassign_to, group_id, user_id
+        )
+        .execute(&mut **tx)
+        .await?;
+    }
+
+    let release_result = sqlx::query!(
+        "DELETE tas FROM TaskAssignees_ tas
+         JOIN Tasks_ t ON tas.task_id = t.task_id
+         JOIN Projects_ p ON t.project_id = p.project_id
+         WHERE p.group_id = ? AND tas.user_id = ?",
+        group_id, user_id
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    // The first assignee of the task moves to reassign_to, or to one of the remaining assignees
+    sqlx::query!(
+        "UPDATE Tasks_ t
+         JOIN Projects_ p ON t.project_id = p.project_id
+         SET t.worker_user_id = COALESCE(?, (SELECT MIN(tas.user_id) FROM TaskAssignees_ tas WHERE tas.task_id = t.task_id))
+         WHERE p.group_id = ? AND t.worker_user_id = ?",
+        reassign_to, group_id, user_id
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    sqlx::query!(
+        "DELETE FROM GroupUserMapping_ WHERE group_id = ? AND user_id = ?",
+        group_id, user_id
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(release_result.rows_affected())
+}
+
+// Handler to remove a worker from the group
+pub async fn remove_worker(
+    pool: web::Data<MySqlPool>,
+    current_user: CurrentUser,
+    request: web::Json<RemoveWorkerRequest>,
+) -> impl Responder {
+    let owner_user_name = &request.owner_user_name;
+    let group_name = &request.group_name;
+    let worker_user_name = &request.worker_user_name;
+    let reassign_to_user_name = &request.reassign_to_user_name;
+
+    // Check that the current user belongs to the group
+    let membership = match require_group_member(pool.get_ref(), &current_user, owner_user_name, group_name).await {
+        Ok(membership) => membership,
+        Err(e) => {
+            info!("Group access denied for {} on {}: {}", current_user.user_name, group_name, e);
+            return HttpResponse::build(e.status_code()).json(RemoveWorkerResponse {
+                success: false,
+                message: e.to_string(),
+            });
+        }
+    };
+    let group_id = membership.group_id;
+
+    // Check that the role of the member allows managing members
+    if !membership.can(Permission::ManageMembers) {
+        info!("{} ({}) is not allowed to manage members of {}", current_user.user_name, membership.role.as_str(), group_name);
+        return HttpResponse::Forbidden().json(RemoveWorkerResponse {
+            success: false,
+            message: "Your role in this group does not allow this action".to_string(),
+        });
+    }
+
+    // Get the worker to remove
+    let (worker_user_id, worker_role) = match find_member(pool.get_ref(), group_id, worker_user_name).await {
+        Ok(Some(member)) => member,
+        Ok(None) => {
+            info!("Worker {} is not a member of group {}", worker_user_name, group_id);
+            return HttpResponse::BadRequest().json(RemoveWorkerResponse {
+                success: false,
+                message: "Worker is not a member of this group".to_string(),
+            });
+        }
+        Err(e) => {
+            error!("Failed to fetch worker {} of group {}: {}", worker_user_name, group_id, e);
+            return HttpResponse::InternalServerError().json(RemoveWorkerResponse {
+                success: false,
+                message: "Failed to fetch worker".to_string(),
+            });
+        }
+    };
+
+    if worker_role == GroupRole::Owner {
+        return HttpResponse::BadRequest().json(RemoveWorkerResponse {
+            success: false,
+            message: "The owner cannot be removed from the group".to_string(),
+        });
+    }
+    if worker_role == GroupRole::Admin && membership.role != GroupRole::Owner {
+        return HttpResponse::Forbidden().json(RemoveWorkerResponse {
+            success: false,
+            message: "Only the owner can remove admins".to_string(),
+        });
+    }
+
+    // Get the member taking over the tasks, if any
+    let reassign_to = if reassign_to_user_name.is_empty() {
+        None
+    } else {
+        match find_member(pool.get_ref(), group_id, reassign_to_user_name).await {
+            Ok(Some((user_id, _))) if user_id != worker_user_id => Some(user_id),
+            Ok(_) => {
+                info!("Cannot reassign tasks of {} to {}", worker_user_name, reassign_to_user_name);
+                return HttpResponse::BadRequest().json(RemoveWorkerResponse {
+                    success: false,
+                    message: "Tasks can only be reassigned to another member of this group".to_string(),
+                });
+            }
+            Err(e) => {
+                error!("Failed to fetch worker {} of group {}: {}", reassign_to_user_name, group_id, e);
+                return HttpResponse::InternalServerError().json(RemoveWorkerResponse {
+                    success: false,
+                    message: "Failed to fetch worker".to_string(),
+                });
+            }
+        }
+    };
+
+    // Start a transaction
+    let mut tx = match pool.begin().await {
+        Ok(transaction) => transaction,
+        Err(e) => {
+            error!("Failed to start a transaction: {}", e);
+            return HttpResponse::InternalServerError().json(RemoveWorkerResponse {
+                success: false,
+                message: "Failed to start a transaction".to_string(),
+            });
+        }
+    };
+
+    let reassigned_count = match release_member(&mut tx, group_id, worker_user_id, reassign_to).await {
+        Ok(count) => count,
+        Err(e) => {
+            error!("Failed to remove worker {} from group {}: {}", worker_user_name, group_id, e);
+            let _ = tx.rollback().await;
+            return HttpResponse::InternalServerError().json(RemoveWorkerResponse {
+                success: false,
+                message: "Failed to remove worker".to_string(),
+            });
+        }
+    };
+
+    if let Err(e) = tx.commit().await {
+        error!("Failed to commit transaction: {}", e);
+        return HttpResponse::InternalServerError().json(RemoveWorkerResponse {
+            success: false,
+            message: "Failed to commit transaction".to_string(),
+        });
+    }
+
+    info!("Worker {} removed from group {}, {} tasks handed over", worker_user_name, group_id, reassigned_count);
+    HttpResponse::Ok().json(RemoveWorkerResponse {
+        success: true,
+        message: format!("Worker removed successfully, {} tasks reassigned", reassigned_count),
+    })
+}
+
+// Handler to change the role of a worker
+pub async fn update_worker_role(
+    pool: web::Data<MySqlPool>,
+    current_user: CurrentUser,
+    request: web::Json<UpdateWorkerRoleRequest>,
+) -> impl Responder {
+    let owner_user_name = &request.owner_user_name;
+    let group_name = &request.group_name;
+    let worker_user_name = &request.worker_user_name;
+    let new_role = request.new_role;
+
+    // Check that the current user belongs to the group
+    let membership = match require_group_member(pool.get_ref(), &current_user, owner_user_name, group_name).await {
+        Ok(membership) => membership,
+        Err(e) => {
+            info!("Group access denied for {} on {}: {}", current_user.user_name, group_name, e);
+            return HttpResponse::build(e.status_code()).json(UpdateWorkerRoleResponse {
+                success: false,
+                message: e.to_string(),
+            });
+        }
+    };
+    let group_id = membership.group_id;
+
+    // Check that the role of the member allows managing members
+    if !membership.can(Permission::ManageMembers) {
+        info!("{} ({}) is not allowed to manage members of {}", current_user.user_name, membership.role.as_str(), group_name);
+        return HttpResponse::Forbidden().json(UpdateWorkerRoleResponse {
+            success: false,
+            message: "Your role in this group does not allow this action".to_string(),
+        });
+    }
+
+    if new_role == GroupRole::Owner {
+        return HttpResponse::BadRequest().json(UpdateWorkerRoleResponse {
+            success: false,
+            message: "Use transfer of ownership to make a member the owner".to_string(),
+        });
+    }
+
+    // Get the worker to update
+    let (worker_user_id, worker_role) = match find_member(pool.get_ref(), group_id, worker_user_name).await {
+        Ok(Some(member)) => member,
+        Ok(None) => {
+            info!("Worker {} is not a member of group {}", worker_user_name, group_id);
+            return HttpResponse::BadRequest().json(UpdateWorkerRoleResponse {
+                success: false,
+                message: "Worker is not a member of this group".to_string(),
+            });
+        }
+        Err(e) => {
+            error!("Failed to fetch worker {} of group {}: {}", worker_user_name, group_id, e);
+            return HttpResponse::InternalServerError().json(UpdateWorkerRoleResponse {
+                success: false,
+                message: "Failed to fetch worker".to_string(),
+            });
+        }
+    };
+
+    if worker_role == GroupRole::Owner {
+        return HttpResponse::BadRequest().json(UpdateWorkerRoleResponse {
+            success: false,
+            message: "The role of the owner cannot be changed".to_string(),
+        });
+    }
+    if (worker_role == GroupRole::Admin || new_role == GroupRole::Admin) && membership.role != GroupRole::Owner {
+        return HttpResponse::Forbidden().json(UpdateWorkerRoleResponse {
+            success: false,
+            message: "Only the owner can appoint or demote admins".to_string(),
+        });
+    }
+
+    let update_result = sqlx::query!(
+        "UPDATE GroupUserMapping_ SET role = ? WHERE group_id = ? AND user_id = ?",
+        new_role.as_str(), group_id, worker_user_id
+    )
+    .execute(pool.get_ref())
+    .await;
+
+    match update_result {
+        Ok(_) => {
+            info!("Role of {} in group {} set to {}", worker_user_name, group_id, new_role.as_str());
+            HttpResponse::Ok().json(UpdateWorkerRoleResponse {
+                success: true,
+                message: "Worker role updated successfully".to_string(),
+            })
+        }
+        Err(e) => {
+            error!("Failed to update role of {} in group {}: {}", worker_user_name, group_id, e);
+            HttpResponse::InternalServerError().json(UpdateWorkerRoleResponse {
+                success: false,
+                message: "Failed to update worker role".to_string(),
+            })
+        }
+    }
+}
+
+// Handler for the current user to leave a group, their tasks are left unassigned
+pub async fn leave_group(
+    pool: web::Data<MySqlPool>,
+    current_user: CurrentUser,
+    request: web::Json<LeaveGroupRequest>,
+) -> impl Responder {
+    let owner_user_name = &request.owner_user_name;
+    let group_name = &request.group_name;
+
+    // Check that the current user belongs to the group
+    let membership = match require_group_member(pool.get_ref(), &current_user, owner_user_name, group_name).await {
+        Ok(membership) => membership,
+        Err(e) => {
+            info!("Group access denied for {} on {}: {}", current_user.user_name, group_name, e);
+            return HttpResponse::build(e.status_code()).json(LeaveGroupResponse {
+                success: false,
+                message: e.to_string(),
+            });
+        }
+    };
+    let group_id = membership.group_id;
+
+    if membership.role == GroupRole::Owner {
+        return HttpResponse::BadRequest().json(LeaveGroupResponse {
+            success: false,
+            message: "The owner cannot leave the group, transfer ownership first".to_string(),
+        });
+    }
+
+    // Start a transaction
+    let mut tx = match pool.begin().await {
+        Ok(transaction) => transaction,
+        Err(e) => {
+            error!("Failed to start a transaction: {}", e);
+            return HttpResponse::InternalServerError().json(LeaveGroupResponse {
+                success: false,
+                message: "Failed to start a transaction".to_string(),
+            });
+        }
+    };
+
+    if let Err(e) = release_member(&mut tx, group_id, current_user.user_id, None).await {
+        error!("Failed to remove {} from group {}: {}", current_user.user_name, group_id, e);
+        let _ = tx.rollback().await;
+        return HttpResponse::InternalServerError().json(LeaveGroupResponse {
+            success: false,
+            message: "Failed to leave group".to_string(),
+        });
+    }
+
+    if let Err(e) = tx.commit().await {
+        error!("Failed to commit transaction: {}", e);
+        return HttpResponse::InternalServerError().json(LeaveGroupResponse {
+            success: false,
+            message: "Failed to commit transaction".to_string(),
+        });
+    }
+
+    info!("{} left group {}", current_user.user_name, group_id);
+    HttpResponse::Ok().json(LeaveGroupResponse {
+        success: true,
+        message: "Left group successfully".to_string(),
+    })
+}
+
+// Handler to create a shareable link to join the group
+pub async fn add_invite_link(
+    pool: web::Data<MySqlPool>,
+    current_user: CurrentUser,
+    request: web::Json<AddInviteLinkRequest>,
+) -> impl Responder {
+    let owner_user_name = &request.owner_user_name;
+    let group_name = &request.group_name;
+    let expires_in_hours = request.expires_in_hours;
+    let max_uses = request.max_uses;
+    let role = match request.role {
+        Some(role) => role,
+        None if request.writeable => GroupRole::Editor,
+        None => GroupRole::Viewer,
+    };
+
+    // Check that the current user belongs to the group
+    let membership = match require_group_member(pool.get_ref(), &current_user, owner_user_name, group_name).await {
+        Ok(membership) => membership,
+        Err(e) => {
+            info!("Group access denied for {} on {}: {}", current_user.user_name, group_name, e);
+            return HttpResponse::build(e.status_code()).json(AddInviteLinkResponse {
+                success: false,
+                message: e.to_string(),
+                token: String::new(),
+            });
+        }
+    };
+    let group_id = membership.group_id;
+
+    // Check that the role of the member allows managing members
+    if !membership.can(Permission::ManageMembers) {
+        info!("{} ({}) is not allowed to manage members of {}", current_user.user_name, membership.role.as_str(), group_name);
+        return HttpResponse::Forbidden().json(AddInviteLinkResponse {
+            success: false,
+            message: "Your role in this group does not allow this action".to_string(),
+            token: String::new(),
+        });
+    }
+
+    // A group has a single owner and only the owner can appoint admins
+    if role == GroupRole::Owner {
+        return HttpResponse::BadRequest().json(AddInviteLinkResponse {
+            success: false,
+            message: "Use transfer of ownership to make a member the owner".to_string(),
+            token: String::new(),
+        });
+    }
+    if role == GroupRole::Admin && membership.role != GroupRole::Owner {
+        return HttpResponse::Forbidden().json(AddInviteLinkResponse {
+            success: false,
+            message: "Only the owner can add admins".to_string(),
+            token: String::new(),
+        });
+    }
+
+    if expires_in_hours.is_some_and(|hours| hours <= 0) || max_uses.is_some_and(|uses| uses <= 0) {
+        return HttpResponse::BadRequest().json(AddInviteLinkResponse {
+            success: false,
+            message: "Expiry and maximum uses must be positive".to_string(),
+            token: String::new(),
+        });
+    }
+
+    if expires_in_hours.is_some_and(|hours| hours > MAX_INVITE_LINK_HOURS) {
+        return HttpResponse::BadRequest().json(AddInviteLinkResponse {
+            success: false,
+            message: format!("Invite links can expire in at most {} hours", MAX_INVITE_LINK_HOURS),
+            token: String::new(),
+        });
+    }
+
+    let expires_at = match expires_in_hours {
+        Some(hours) => match Duration::try_hours(hours).and_then(|duration| Utc::now().checked_add_signed(duration)) {
+            Some(expires_at) => Some(expires_at),
+            None => {
+                return HttpResponse::BadRequest().json(AddInviteLinkResponse {
+                    success: false,
+                    message: "Invalid expiry".to_string(),
+                    token: String::new(),
+                });
+            }
+        },
+        None => None,
+    };
+
+    let token = Uuid::new_v4().simple().to_string();
+
+    let insert_result = sqlx::query!(
+        "INSERT INTO GroupInviteLinks_ (group_id, token, role, created_by_user_id, expires_at, max_uses)
+         VALUES (?, ?, ?, ?, ?, ?)",
+        group_id, token, role.as_str(), current_user.user_id, expires_at, max_uses
+    )
+    .execute(pool.get_ref())
+    .await;
+
+    match insert_result {
+        Ok(_) => {
+            info!("{} created an invite link for group {}", current_user.user_name, group_id);
+            HttpResponse::Ok().json(AddInviteLinkResponse {
+                success: true,
+                message: "Invite link created successfully".to_string(),
+                token,
+            })
+        }
+        Err(e) => {
+            error!("Failed to create invite link for group {}: {}", group_id, e);
+            HttpResponse::InternalServerError().json(AddInviteLinkResponse {
+                success: false,
+                message: "Failed to create invite link".to_string(),
+                token: String::new(),
+            })
+        }
+    }
+}
+
+// Handler to get the invite links of the group
+pub async fn get_invite_link_list(
+    pool: web::Data<MySqlPool>,
+    current_user: CurrentUser,
+    request: web::Json<GetInviteLinkListRequest>,
+) -> impl Responder {
+    let owner_user_name = &request.owner_user_name;
+    let group_name = &request.group_name;
+
+    // Check that the current user belongs to the group
+    let membership = match require_group_member(pool.get_ref(), &current_user, owner_user_name, group_name).await {
+        Ok(membership) => membership,
+        Err(e) => {
+            info!("Group access denied for {} on {}: {}", current_user.user_name, group_name, e);
+            return HttpResponse::build(e.status_code()).json(GetInviteLinkListResponse { invite_links: Vec::new() });
+        }
+    };
+    let group_id = membership.group_id;
+
+    // Tokens are only shown to members who can manage members
+    if !membership.can(Permission::ManageMembers) {
+        info!("{} ({}) is not allowed to manage members of {}", current_user.user_name, membership.role.as_str(), group_name);
+        return HttpResponse::Forbidden().json(GetInviteLinkListResponse { invite_links: Vec::new() });
+    }
+
+    let links_result = sqlx::query!(
+        "SELECT l.token, l.role, u.user_name AS created_by, l.created_at, l.expires_at, l.max_uses, l.use_count
+         FROM GroupInviteLinks_ l
+         JOIN Users_ u ON l.created_by_user_id = u.user_id
+         WHERE l.group_id = ?
+         ORDER BY l.created_at",
+        group_id
+    )
+    .fetch_all(pool.get_ref())
+    .await;
+
+    match links_result {
+        Ok(records) => {
+            let invite_links: Vec<InviteLink> = records.into_iter().map(|record| InviteLink {
+                token: record.token,
+                role: record.role,
+                created_by: record.created_by,
+                created_at: record.created_at.to_string(),
+                expires_at: record.expires_at.map(|expires_at| expires_at.to_string()),
+                max_uses: record.max_uses,
+                use_count: record.use_count,
+            }).collect();
+
+            HttpResponse::Ok().json(GetInviteLinkListResponse { invite_links })
+        }
+        Err(e) => {
+            error!("Failed to fetch invite links for group_id {}: {}", group_id, e);
+            HttpResponse::InternalServerError().json(GetInviteLinkListResponse { invite_links: Vec::new() })
+        }
+    }
+}
+
+// Handler to revoke an invite link
+pub async fn delete_invite_link(
+    pool: web::Data<MySqlPool>,
+    current_user: CurrentUser,
+    request: web::Json<DeleteInviteLinkRequest>,
+) -> impl Responder {
+    let owner_user_name = &request.owner_user_name;
+    let group_name = &request.group_name;
+    let token = &request.token;
+
+    // Check that the current user belongs to the group
+    let membership = match require_group_member(pool.get_ref(), &current_user, owner_user_name, group_name).await {
+        Ok(membership) => membership,
+        Err(e) => {
+            info!("Group access denied for {} on {}: {}", current_user.user_name, group_name, e);
+            return HttpResponse::build(e.status_code()).json(DeleteInviteLinkResponse {
+                success: false,
+                message: e.to_string(),
+            });
+        }
+    };
+    let group_id = membership.group_id;
+
+    // Check that the role of the member allows managing members
+    if !membership.can(Permission::ManageMembers) {
+        info!("{} ({}) is not allowed to manage members of {}", current_user.user_name, membership.role.as_str(), group_name);
+        return HttpResponse::Forbidden().json(DeleteInviteLinkResponse {
+            success: false,
+            message: "Your role in this group does not allow this action".to_string(),
+        });
+    }
+
+    let delete_result = sqlx::query!(
+        "DELETE FROM GroupInviteLinks_ WHERE group_id = ? AND token = ?",
+        group_id, token
+    )
+    .execute(pool.get_ref())
+    .await;
+
+    match delete_result {
+        Ok(result) if result.rows_affected() == 0 => {
+            info!("Invite link not found in group {}", group_id);
+            HttpResponse::BadRequest().json(DeleteInviteLinkResponse {
+                success: false,
+                message: "Invite link not found".to_string(),
+            })
+        }
+        Ok(_) => HttpResponse::Ok().json(DeleteInviteLinkResponse {
+            success: true,
+            message: "Invite link deleted successfully".to_string(),
+        }),
+        Err(e) => {
+            error!("Failed to delete invite link of group {}: {}", group_id, e);
+            HttpResponse::InternalServerError().json(DeleteInviteLinkResponse {
+                success: false,
+                message: "Failed to delete invite link".to_string(),
+            })
+        }
+    }
+}
+
+// Handler to get the task workflow of the group
+pub async fn get_workflow(
     pool: web::Data<MySqlPool>,
-    _: HttpRequest,
-    request: web::Json<GetWorkerListRequest>,
+    current_user: CurrentUser,
+    request: web::Json<GetWorkflowRequest>,
 ) -> impl Responder {
     let owner_user_name = &request.owner_user_name;
     let group_name = &request.group_name;
-    
-    // Get the group_id with group_name from Groups_
-    let group_id_result = sqlx::query!(
-        "
-        SELECT g.group_id 
-        FROM Groups_ g
-        JOIN Users_ u ON g.owner_user_id = u.user_id
-        WHERE g.group_name = ? AND u.user_name = ?
-        ",
-        group_name, owner_user_name
-    )
-    .fetch_one(pool.get_ref())
-    .await;
 
-    let group_id = match group_id_result {
-        Ok(record) => record.group_id,
-        Err(_) => {
-            info!("Group not found: {}", group_name);
-            return HttpResponse::BadRequest().json(GetWorkerListResponse { workers: Vec::new() });
+    // Check that the current user belongs to the group
+    let group_id = match require_group_member(pool.get_ref(), &current_user, owner_user_name, group_name).await {
+        Ok(membership) => membership.group_id,
+        Err(e) => {
+            info!("Group access denied for {} on {}: {}", current_user.user_name, group_name, e);
+            return HttpResponse::build(e.status_code()).json(GetWorkflowResponse {
+                states: Vec::new(),
+                transitions: Vec::new(),
+            });
         }
     };
 
-    // Get the worker list from GroupUserMapping_ where group_id is the one from the previous query
-    let workers_result = sqlx::query!(
-        "SELECT u.user_name, u.user_email 
-         FROM GroupUserMapping_ gum
-         JOIN Users_ u ON gum.user_id = u.user_id
-         WHERE gum.group_id = ? AND u.user_name != ?",
-        group_id, owner_user_name
-    )
-    .fetch_all(pool.get_ref())
-    .await;
-
-    match workers_result {
-        Ok(records) => {
-            let workers: Vec<Worker> = records.into_iter().map(|record| Worker {
-                user_name: record.user_name,
-                user_email: record.user_email,
-            }).collect();
-
-            HttpResponse::Ok().json(GetWorkerListResponse { workers })
-        },
+    match fetch_workflow(pool.get_ref(), group_id).await {
+        Ok(workflow) => HttpResponse::Ok().json(GetWorkflowResponse {
+            states: workflow.states,
+            transitions: workflow.transitions.into_iter().map(|(from_state, to_state)| WorkflowTransition {
+                from_state,
+                to_state,
+            }).collect(),
+        }),
         Err(e) => {
-            error!("Failed to fetch workers for group_id {}: {}", group_id, e);
-            HttpResponse::InternalServerError().json(GetWorkerListResponse { workers: Vec::new() })
+            error!("Failed to fetch workflow of group {}: {}", group_id, e);
+            HttpResponse::InternalServerError().json(GetWorkflowResponse {
+                states: Vec::new(),
+                transitions: Vec::new(),
+            })
         }
     }
 }
 
-// Handler to add a worker
-pub async fn add_worker(
+// Handler to replace the task workflow of the group
+pub async fn update_workflow(
     pool: web::Data<MySqlPool>,
-    req: HttpRequest,
-    request: web::Json<AddWorkerRequest>,
+    current_user: CurrentUser,
+    request: web::Json<UpdateWorkflowRequest>,
 ) -> impl Responder {
     let owner_user_name = &request.owner_user_name;
     let group_name = &request.group_name;
-    let worker_user_name = &request.worker_user_name;
 
-    // Get the current user name using session ID in the cookie
-    let session_id = match req.cookie("session_id") {
-        Some(cookie) => cookie.value().to_string(),
-        None => {
-            info!("Session ID not found in cookies for add_worker");
-            return HttpResponse::BadRequest().json(AddWorkerResponse {
+    // Check that the current user belongs to the group
+    let membership = match require_group_member(pool.get_ref(), &current_user, owner_user_name, group_name).await {
+        Ok(membership) => membership,
+        Err(e) => {
+            info!("Group access denied for {} on {}: {}", current_user.user_name, group_name, e);
+            return HttpResponse::build(e.status_code()).json(UpdateWorkflowResponse {
                 success: false,
-                message: "Session ID not found".to_string(),
+                message: e.to_string(),
             });
         }
     };
+    let group_id = membership.group_id;
 
-    let session_result = sqlx::query!(
-        "SELECT u.user_name FROM Sessions_ s
-         JOIN Users_ u ON s.user_id = u.user_id
-         WHERE s.session_id = ? AND s.expires_at > NOW()",
-        session_id
-    )
-    .fetch_one(pool.get_ref())
-    .await;
+    // Check that the role of the member allows managing the workflow
+    if !membership.can(Permission::ManageWorkflow) {
+        info!("{} ({}) is not allowed to manage the workflow of {}", current_user.user_name, membership.role.as_str(), group_name);
+        return HttpResponse::Forbidden().json(UpdateWorkflowResponse {
+            success: false,
+            message: "Your role in this group does not allow this action".to_string(),
+        });
+    }
 
-    let current_user_name = match session_result {
-        Ok(session) => session.user_name,
-        Err(_) => {
-            info!("Invalid or expired session ID: {}", session_id);
-            return HttpResponse::BadRequest().json(AddWorkerResponse {
-                success: false,
-                message: "Invalid or expired session ID".to_string(),
-            });
+    // An empty list of states resets the group to the built-in workflow
+    let custom = !request.states.is_empty();
+    let workflow = if custom {
+        Workflow {
+            states: request.states.clone(),
+            transitions: request.transitions.iter().map(|t| (t.from_state.clone(), t.to_state.clone())).collect(),
         }
+    } else {
+        Workflow::builtin()
     };
 
-    // Assert owner_user_name == current user name
-    if owner_user_name != &current_user_name {
-        return HttpResponse::BadRequest().json(AddWorkerResponse {
+    if let Err(reason) = workflow.validate() {
+        return HttpResponse::BadRequest().json(UpdateWorkflowResponse {
             success: false,
-            message: "Unauthorized action".to_string(),
+            message: reason,
         });
     }
 
-    // Check if worker_user_name exists in Users_ table
-    let worker_result = sqlx::query!(
-        "SELECT user_id FROM Users_ WHERE user_name = ?",
-        worker_user_name
+    // Every task of the group must stay in a state of the new workflow
+    let used_states_result = sqlx::query_scalar!(
+        "SELECT DISTINCT t.status
+         FROM Tasks_ t
+         JOIN Projects_ p ON t.project_id = p.project_id
+         WHERE p.group_id = ?",
+        group_id
     )
-    .fetch_one(pool.get_ref())
+    .fetch_all(pool.get_ref())
     .await;
 
-    let worker_user_id = match worker_result {
-        Ok(record) => record.user_id,
-        Err(_) => {
-            info!("Worker not found: {}", worker_user_name);
-            return HttpResponse::BadRequest().json(AddWorkerResponse {
+    let missing_states: Vec<String> = match used_states_result {
+        Ok(states) => states.into_iter().filter(|state| !workflow.has_state(state)).collect(),
+        Err(e) => {
+            error!("Failed to fetch task statuses of group {}: {}", group_id, e);
+            return HttpResponse::InternalServerError().json(UpdateWorkflowResponse {
                 success: false,
-                message: "Worker not found".to_string(),
+                message: "Failed to fetch task statuses".to_string(),
             });
         }
     };
 
-    // Get the group_id with group_name from Groups_
-    let group_id_result = sqlx::query!(
-        "
-        SELECT g.group_id 
-        FROM Groups_ g
-        JOIN Users_ u ON g.owner_user_id = u.user_id
-        WHERE g.group_name = ? AND u.user_name = ?
-        ",
-        group_name, owner_user_name
-    )
-    .fetch_one(pool.get_ref())
-    .await;
+    if !missing_states.is_empty() {
+        return HttpResponse::BadRequest().json(UpdateWorkflowResponse {
+            success: false,
+            message: format!("Tasks are still in removed states: {}", missing_states.join(", ")),
+        });
+    }
 
-    let group_id = match group_id_result {
-        Ok(record) => record.group_id,
-        Err(_) => {
-            info!("Group not found: {}", group_name);
-            return HttpResponse::BadRequest().json(AddWorkerResponse {
+    // Start a transaction
+    let mut tx = match pool.begin().await {
+        Ok(transaction) => transaction,
+        Err(e) => {
+            error!("Failed to start a transaction: {}", e);
+            return HttpResponse::InternalServerError().json(UpdateWorkflowResponse {
                 success: false,
-                message: "Group not found".to_string(),
+                message: "Failed to start a transaction".to_string(),
             });
         }
     };
 
-    // Add worker to GroupUserMapping_ with writeable set to 0
-    let insert_result = sqlx::query!(
-        "INSERT INTO GroupUserMapping_ (group_id, user_id, writeable) VALUES (?, ?, false)",
-        group_id, worker_user_id
-    )
-    .execute(pool.get_ref())
-    .await;
+    if let Err(e) = replace_workflow(&mut tx, group_id, custom.then_some(&workflow)).await {
+        error!("Failed to update workflow of group {}: {}", group_id, e);
+        let _ = tx.rollback().await;
+        return HttpResponse::InternalServerError().json(UpdateWorkflowResponse {
+            success: false,
+            message: "Failed to update workflow".to_string(),
+        });
+    }
 
-    match insert_result {
-        Ok(_) => HttpResponse::Ok().json(AddWorkerResponse {
-            success: true,
-            message: "Worker added successfully".to_string(),
-        }),
-        Err(e) => {
-            error!("Failed to add worker to group {}: {}", group_id, e);
-            HttpResponse::InternalServerError().json(AddWorkerResponse {
-                success: false,
-                message: "Failed to add worker".to_string(),
-            })
-        }
+    if let Err(e) = tx.commit().await {
+        error!("Failed to commit transaction: {}", e);
+        return HttpResponse::InternalServerError().json(UpdateWorkflowResponse {
+            success: false,
+            message: "Failed to commit transaction".to_string(),
+        });
+    }
+
+    info!("{} updated the workflow of group {}", current_user.user_name, group_id);
+    HttpResponse::Ok().json(UpdateWorkflowResponse {
+        success: true,
+        message: "Workflow updated successfully".to_string(),
+    })
+}
+
+// Replace the stored workflow of a group; None leaves the group on the built-in workflow
+async fn replace_workflow(
+    tx: &mut sqlx::Transaction<'_, sqlx::MySql>,
+    group_id: i32,
+    workflow: Option<&Workflow>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!("DELETE FROM WorkflowTransitions_ WHERE group_id = ?", group_id)
+        .execute(&mut **tx)
+        .await?;
+    sqlx::query!("DELETE FROM WorkflowStates_ WHERE group_id = ?", group_id)
+        .execute(&mut **tx)
+        .await?;
+
+    let workflow = match workflow {
+        Some(workflow) => workflow,
+        None => return Ok(()),
+    };
+
+    for (position, state) in workflow.states.iter().enumerate() {
+        sqlx::query!(
+            "INSERT INTO WorkflowStates_ (group_id, state_name, position) VALUES (?, ?, ?)",
+            group_id, state, position as i32
+        )
+        .execute(&mut **tx)
+        .await?;
     }
+
+    for (from_state, to_state) in &workflow.transitions {
+        sqlx::query!(
+            "INSERT INTO WorkflowTransitions_ (group_id, from_state, to_state) VALUES (?, ?, ?)",
+            group_id, from_state, to_state
+        )
+        .execute(&mut **tx)
+        .await?;
+    }
+
+    Ok(())
 }
 
 // Handler to get the tag list
 pub async fn get_tag_list(
     pool: web::Data<MySqlPool>,
-    _: HttpRequest,
+    current_user: CurrentUser,
     request: web::Json<GetTagListRequest>,
 ) -> impl Responder {
     let group_name = &request.group_name;
     let owner_user_name = &request.owner_user_name;
 
-    // Get group_id using group_name from Groups_
-    let group_id_result = sqlx::query!(
-        "
-        SELECT g.group_id 
-        FROM Groups_ g
-        JOIN Users_ u ON g.owner_user_id = u.user_id
-        WHERE g.group_name = ? AND u.user_name = ?
-        ",
-        group_name, owner_user_name
-    )
-    .fetch_one(pool.get_ref())
-    .await;
-
-    let group_id = match group_id_result {
-        Ok(record) => record.group_id,
-        Err(_) => {
-            info!("Group not found: {}", group_name);
-            return HttpResponse::BadRequest().json(GetTagListResponse { tags: Vec::new() });
+    // Check that the current user belongs to the group
+    let group_id = match require_group_member(pool.get_ref(), &current_user, owner_user_name, group_name).await {
+        Ok(membership) => membership.group_id,
+        Err(e) => {
+            info!("Group access denied for {} on {}: {}", current_user.user_name, group_name, e);
+            return HttpResponse::build(e.status_code()).json(GetTagListResponse { tags: Vec::new() });
         }
     };
 
@@ -250,7 +1071,7 @@ pub async fn get_tag_list(
 // Handler to add tag
 pub async fn add_tag(
     pool: web::Data<MySqlPool>,
-    req: HttpRequest,
+    current_user: CurrentUser,
     request: web::Json<AddTagRequest>,
 ) -> impl Responder {
     let owner_user_name = &request.owner_user_name;
@@ -258,70 +1079,28 @@ pub async fn add_tag(
     let tag_name = &request.tag_name;
     let tag_color = &request.tag_color;
 
-    // Get the current user name using session ID in the cookie
-    let session_id = match req.cookie("session_id") {
-        Some(cookie) => cookie.value().to_string(),
-        None => {
-            info!("Session ID not found in cookies for add_tag");
-            return HttpResponse::BadRequest().json(AddTagResponse {
-                success: false,
-                message: "Session ID not found".to_string(),
-            });
-        }
-    };
-
-    let session_result = sqlx::query!(
-        "SELECT u.user_name FROM Sessions_ s
-         JOIN Users_ u ON s.user_id = u.user_id
-         WHERE s.session_id = ? AND s.expires_at > NOW()",
-        session_id
-    )
-    .fetch_one(pool.get_ref())
-    .await;
-
-    let current_user_name = match session_result {
-        Ok(session) => session.user_name,
-        Err(_) => {
-            info!("Invalid or expired session ID: {}", session_id);
-            return HttpResponse::BadRequest().json(AddTagResponse {
+    // Check that the current user belongs to the group
+    let membership = match require_group_member(pool.get_ref(), &current_user, owner_user_name, group_name).await {
+        Ok(membership) => membership,
+        Err(e) => {
+            info!("Group access denied for {} on {}: {}", current_user.user_name, group_name, e);
+            return HttpResponse::build(e.status_code()).json(AddTagResponse {
                 success: false,
-                message: "Invalid or expired session ID".to_string(),
+                message: e.to_string(),
             });
         }
     };
+    let group_id = membership.group_id;
 
-    // Assert owner_user_name == current user name
-    if owner_user_name != &current_user_name {
-        return HttpResponse::BadRequest().json(AddTagResponse {
+    // Check that the role of the member allows managing tags
+    if !membership.can(Permission::ManageTags) {
+        info!("{} ({}) is not allowed to manage tags of {}", current_user.user_name, membership.role.as_str(), group_name);
+        return HttpResponse::Forbidden().json(AddTagResponse {
             success: false,
-            message: "Unauthorized action".to_string(),
+            message: "Your role in this group does not allow this action".to_string(),
         });
     }
 
-    // Get the group_id with group_name from Groups_
-    let group_id_result = sqlx::query!(
-        "
-        SELECT g.group_id 
-        FROM Groups_ g
-        JOIN Users_ u ON g.owner_user_id = u.user_id
-        WHERE g.group_name = ? AND u.user_name = ?
-        ",
-        group_name, owner_user_name
-    )
-    .fetch_one(pool.get_ref())
-    .await;
-
-    let group_id = match group_id_result {
-        Ok(record) => record.group_id,
-        Err(_) => {
-            info!("Group not found: {}", group_name);
-            return HttpResponse::BadRequest().json(AddTagResponse {
-                success: false,
-                message: "Group not found".to_string(),
-            });
-        }
-    };
-
     // Add tag to Tags_ table
     let insert_result = sqlx::query!(
         "INSERT INTO Tags_ (group_id, tag_name, tag_color) VALUES (?, ?, ?)",
@@ -347,7 +1126,7 @@ pub async fn add_tag(
 
 pub async fn update_tag(
     pool: web::Data<MySqlPool>,
-    req: HttpRequest,
+    current_user: CurrentUser,
     request: web::Json<UpdateTagRequest>,
 ) -> impl Responder {
     let owner_user_name = &request.owner_user_name;
@@ -356,70 +1135,28 @@ pub async fn update_tag(
     let new_tag_name = &request.new_tag_name;
     let new_tag_color = &request.new_tag_color;
 
-    // Get the current user name using session ID in the cookie
-    let session_id = match req.cookie("session_id") {
-        Some(cookie) => cookie.value().to_string(),
-        None => {
-            info!("Session ID not found in cookies for update_tag");
-            return HttpResponse::BadRequest().json(UpdateTagResponse {
-                success: false,
-                message: "Session ID not found".to_string(),
-            });
-        }
-    };
-
-    let session_result = sqlx::query!(
-        "SELECT u.user_name FROM Sessions_ s
-         JOIN Users_ u ON s.user_id = u.user_id
-         WHERE s.session_id = ? AND s.expires_at > NOW()",
-        session_id
-    )
-    .fetch_one(pool.get_ref())
-    .await;
-
-    let current_user_name = match session_result {
-        Ok(session) => session.user_name,
-        Err(_) => {
-            info!("Invalid or expired session ID: {}", session_id);
-            return HttpResponse::BadRequest().json(UpdateTagResponse {
+    // Check that the current user belongs to the group
+    let membership = match require_group_member(pool.get_ref(), &current_user, owner_user_name, group_name).await {
+        Ok(membership) => membership,
+        Err(e) => {
+            info!("Group access denied for {} on {}: {}", current_user.user_name, group_name, e);
+            return HttpResponse::build(e.status_code()).json(UpdateTagResponse {
                 success: false,
-                message: "Invalid or expired session ID".to_string(),
+                message: e.to_string(),
             });
         }
     };
+    let group_id = membership.group_id;
 
-    // Assert owner_user_name == current user name
-    if owner_user_name != &current_user_name {
-        return HttpResponse::BadRequest().json(UpdateTagResponse {
+    // Check that the role of the member allows managing tags
+    if !membership.can(Permission::ManageTags) {
+        info!("{} ({}) is not allowed to manage tags of {}", current_user.user_name, membership.role.as_str(), group_name);
+        return HttpResponse::Forbidden().json(UpdateTagResponse {
             success: false,
-            message: "Unauthorized action".to_string(),
+            message: "Your role in this group does not allow this action".to_string(),
         });
     }
 
-    // Get the group_id with group_name from Groups_
-    let group_id_result = sqlx::query!(
-        "
-        SELECT g.group_id 
-        FROM Groups_ g
-        JOIN Users_ u ON g.owner_user_id = u.user_id
-        WHERE g.group_name = ? AND u.user_name = ?
-        ",
-        group_name, owner_user_name
-    )
-    .fetch_one(pool.get_ref())
-    .await;
-
-    let group_id = match group_id_result {
-        Ok(record) => record.group_id,
-        Err(_) => {
-            info!("Group not found: {}", group_name);
-            return HttpResponse::BadRequest().json(UpdateTagResponse {
-                success: false,
-                message: "Group not found".to_string(),
-            });
-        }
-    };
-
     // Check if tag_name exists in Tags_ table under the group
     let tag_result = sqlx::query!(
         "SELECT tag_id, tag_name, tag_color FROM Tags_ WHERE group_id = ? AND tag_name = ?",
@@ -477,77 +1214,35 @@ pub async fn update_tag(
 
 pub async fn delete_tag(
     pool: web::Data<MySqlPool>,
-    req: HttpRequest,
+    current_user: CurrentUser,
     request: web::Json<DeleteTagRequest>,
 ) -> impl Responder {
     let owner_user_name = &request.owner_user_name;
     let group_name = &request.group_name;
     let tag_name = &request.tag_name;
 
-    // Get the current user name using session ID in the cookie
-    let session_id = match req.cookie("session_id") {
-        Some(cookie) => cookie.value().to_string(),
-        None => {
-            info!("Session ID not found in cookies for delete_tag");
-            return HttpResponse::BadRequest().json(DeleteTagResponse {
-                success: false,
-                message: "Session ID not found".to_string(),
-            });
-        }
-    };
-
-    let session_result = sqlx::query!(
-        "SELECT u.user_name FROM Sessions_ s
-         JOIN Users_ u ON s.user_id = u.user_id
-         WHERE s.session_id = ? AND s.expires_at > NOW()",
-        session_id
-    )
-    .fetch_one(pool.get_ref())
-    .await;
-
-    let current_user_name = match session_result {
-        Ok(session) => session.user_name,
-        Err(_) => {
-            info!("Invalid or expired session ID: {}", session_id);
-            return HttpResponse::BadRequest().json(DeleteTagResponse {
+    // Check that the current user belongs to the group
+    let membership = match require_group_member(pool.get_ref(), &current_user, owner_user_name, group_name).await {
+        Ok(membership) => membership,
+        Err(e) => {
+            info!("Group access denied for {} on {}: {}", current_user.user_name, group_name, e);
+            return HttpResponse::build(e.status_code()).json(DeleteTagResponse {
                 success: false,
-                message: "Invalid or expired session ID".to_string(),
+                message: e.to_string(),
             });
         }
     };
+    let group_id = membership.group_id;
 
-    // Assert owner_user_name == current user name
-    if owner_user_name != &current_user_name {
-        return HttpResponse::BadRequest().json(DeleteTagResponse {
+    // Check that the role of the member allows managing tags
+    if !membership.can(Permission::ManageTags) {
+        info!("{} ({}) is not allowed to manage tags of {}", current_user.user_name, membership.role.as_str(), group_name);
+        return HttpResponse::Forbidden().json(DeleteTagResponse {
             success: false,
-            message: "Unauthorized action".to_string(),
+            message: "Your role in this group does not allow this action".to_string(),
         });
     }
 
-    // Get the group_id with group_name from Groups_
-    let group_id_result = sqlx::query!(
-        "
-        SELECT g.group_id 
-        FROM Groups_ g
-        JOIN Users_ u ON g.owner_user_id = u.user_id
-        WHERE g.group_name = ? AND u.user_name = ?
-        ",
-        group_name, owner_user_name
-    )
-    .fetch_one(pool.get_ref())
-    .await;
-
-    let group_id = match group_id_result {
-        Ok(record) => record.group_id,
-        Err(_) => {
-            info!("Group not found: {}", group_name);
-            return HttpResponse::BadRequest().json(DeleteTagResponse {
-                success: false,
-                message: "Group not found".to_string(),
-            });
-        }
-    };
-
     // Check if the tag exists and get its ID
     let tag_id_result = sqlx::query!(
         "SELECT tag_id FROM Tags_ WHERE group_id = ? AND tag_name = ?",
@@ -651,31 +1346,20 @@ pub async fn delete_tag(
 // Handler to get task list by tag list
 pub async fn get_task_list_by_tag_list(
     pool: web::Data<MySqlPool>,
-    _: HttpRequest,
+    current_user: CurrentUser,
     request: web::Json<GetTaskListByTagListRequest>,
 ) -> impl Responder {
     let owner_user_name = &request.owner_user_name;
     let group_name = &request.group_name;
     let tags = &request.tags;
+    let status_filter = &request.status_filter;
     info!("get_task_list_by_tag_list");
-    // Get group_id using group_name from Groups_
-    let group_id_result = sqlx::query!(
-        "
-        SELECT g.group_id 
-        FROM Groups_ g
-        JOIN Users_ u ON g.owner_user_id = u.user_id
-        WHERE g.group_name = ? AND u.user_name = ?
-        ",
-        group_name, owner_user_name
-    )
-    .fetch_one(pool.get_ref())
-    .await;
-
-    let group_id = match group_id_result {
-        Ok(record) => record.group_id,
-        Err(_) => {
-            info!("Group not found: {}", group_name);
-            return HttpResponse::BadRequest().json(GetTaskListByTagListResponse { tasks: Vec::new() });
+    // Check that the current user belongs to the group
+    let group_id = match require_group_member(pool.get_ref(), &current_user, owner_user_name, group_name).await {
+        Ok(membership) => membership.group_id,
+        Err(e) => {
+            info!("Group access denied for {} on {}: {}", current_user.user_name, group_name, e);
+            return HttpResponse::build(e.status_code()).json(GetTaskListByTagListResponse { tasks: Vec::new() });
         }
     };
 
@@ -683,14 +1367,17 @@ pub async fn get_task_list_by_tag_list(
         // Tag list is empty, get all tasks under the group
         let tasks_result = sqlx::query!(
             "SELECT t.title AS task_title, 
-                    u.user_name AS worker_name, 
+                    COALESCE(u.user_name, '') AS `worker_name!`, 
+                    (SELECT GROUP_CONCAT(au.user_name ORDER BY au.user_name SEPARATOR ',') FROM TaskAssignees_ tas JOIN Users_ au ON tas.user_id = au.user_id WHERE tas.task_id = t.task_id) AS worker_names, 
                     t.start_time, 
                     t.end_time, 
                     t.description, 
+                    t.status, 
+                    t.progress, 
                     p.project_name, 
                     GROUP_CONCAT(DISTINCT ta.tag_color SEPARATOR ',') AS tag_colors
             FROM Tasks_ t
-            JOIN Users_ u ON t.worker_user_id = u.user_id
+            LEFT JOIN Users_ u ON t.worker_user_id = u.user_id
             JOIN Projects_ p ON t.project_id = p.project_id
             JOIN TagProjectMapping_ tpm ON p.project_id = tpm.project_id
             JOIN Tags_ ta ON ta.tag_Id = tpm.tag_id
@@ -706,12 +1393,15 @@ pub async fn get_task_list_by_tag_list(
                 let tasks: Vec<Task> = records.into_iter().map(|record| Task {
                     task_title: record.task_title,
                     worker_name: record.worker_name,
+                    worker_names: record.worker_names.map(|names| names.split(',').map(|s| s.to_string()).collect()).unwrap_or_default(),
                     start_time: record.start_time.to_string(),
                     end_time: record.end_time.to_string(),
                     description: record.description,
                     project_name: record.project_name,
                     tag_colors: record.tag_colors.expect("TAG COLORS EMPTY, NEVER").split(',').map(|s| s.to_string()).collect(),
-                }).collect();
+                    status: record.status,
+                    progress: record.progress,
+                }).filter(|task| status_filter.is_empty() || status_filter.contains(&task.status)).collect();
 
                 HttpResponse::Ok().json(GetTaskListByTagListResponse { tasks })
             },
@@ -787,14 +1477,17 @@ pub async fn get_task_list_by_tag_list(
         let placeholders = project_ids.iter().map(|_| "?").collect::<Vec<_>>().join(",");
         let query_str = format!(
             "SELECT t.title AS task_title, 
-                    u.user_name AS worker_name, 
+                    COALESCE(u.user_name, '') AS worker_name, 
+                    (SELECT GROUP_CONCAT(au.user_name ORDER BY au.user_name SEPARATOR ',') FROM TaskAssignees_ tas JOIN Users_ au ON tas.user_id = au.user_id WHERE tas.task_id = t.task_id) AS worker_names, 
                     t.start_time, 
                     t.end_time, 
                     t.description,  
+                    t.status, 
+                    t.progress, 
                     p.project_name, 
                     GROUP_CONCAT(DISTINCT ta.tag_color SEPARATOR ',') AS tag_colors
             FROM Tasks_ t
-            JOIN Users_ u ON t.worker_user_id = u.user_id
+            LEFT JOIN Users_ u ON t.worker_user_id = u.user_id
             JOIN Projects_ p ON t.project_id = p.project_id
             LEFT JOIN TagProjectMapping_ tpm ON t.project_id = tpm.project_id
             LEFT JOIN Tags_ ta ON tpm.tag_id = ta.tag_id AND ta.group_id = ?
@@ -815,12 +1508,15 @@ pub async fn get_task_list_by_tag_list(
                 let tasks: Vec<Task> = records.into_iter().map(|record| Task {
                     task_title: record.get("task_title"),
                     worker_name: record.get("worker_name"),
+                    worker_names: record.get::<Option<String>, _>("worker_names").map(|names| names.split(',').map(|s| s.to_string()).collect()).unwrap_or_default(),
                     start_time: record.get::<PrimitiveDateTime, _>("start_time").to_string(),
                     end_time: record.get::<PrimitiveDateTime, _>("end_time").to_string(),
                     description: record.get("description"),
                     project_name: record.get("project_name"),
                     tag_colors: record.get::<Option<String>, _>("tag_colors").unwrap_or_default().split(',').map(|s| s.to_string()).collect(),
-                }).collect();
+                    status: record.get("status"),
+                    progress: record.get("progress"),
+                }).filter(|task| status_filter.is_empty() || status_filter.contains(&task.status)).collect();
 
                 HttpResponse::Ok().json(GetTaskListByTagListResponse { tasks })
             },
@@ -836,35 +1532,24 @@ pub async fn get_task_list_by_tag_list(
 // Handler to get task list by project name
 pub async fn get_task_list_by_project_name(
     pool: web::Data<MySqlPool>,
-    _: HttpRequest,
+    current_user: CurrentUser,
     request: web::Json<GetTaskListByProjectNameRequest>,
 ) -> impl Responder {
     let owner_user_name = &request.owner_user_name;
     let group_name = &request.group_name;
     let project_name = &request.project_name;
+    let status_filter = &request.status_filter;
 
     if project_name.is_empty() {
-        return HttpResponse::BadRequest().json(GetTaskListByProjectNameResponse { tasks: Vec::new() });
+        return HttpResponse::BadRequest().json(GetTaskListByProjectNameResponse { tasks: Vec::new(), dependencies: Vec::new() });
     }
 
-    // Get group_id using group_name from Groups_
-    let group_id_result = sqlx::query!(
-        "
-        SELECT g.group_id 
-        FROM Groups_ g
-        JOIN Users_ u ON g.owner_user_id = u.user_id
-        WHERE g.group_name = ? AND u.user_name = ?
-        ",
-        group_name, owner_user_name
-    )
-    .fetch_one(pool.get_ref())
-    .await;
-
-    let group_id = match group_id_result {
-        Ok(record) => record.group_id,
-        Err(_) => {
-            info!("Group not found: {}", group_name);
-            return HttpResponse::BadRequest().json(GetTaskListByProjectNameResponse { tasks: Vec::new() });
+    // Check that the current user belongs to the group
+    let group_id = match require_group_member(pool.get_ref(), &current_user, owner_user_name, group_name).await {
+        Ok(membership) => membership.group_id,
+        Err(e) => {
+            info!("Group access denied for {} on {}: {}", current_user.user_name, group_name, e);
+            return HttpResponse::build(e.status_code()).json(GetTaskListByProjectNameResponse { tasks: Vec::new(), dependencies: Vec::new() });
         }
     };
 
@@ -880,22 +1565,29 @@ pub async fn get_task_list_by_project_name(
         Ok(records) => records.into_iter().map(|record| record.project_id).collect(),
         Err(_) => {
             info!("Projects not found for group: {}", group_name);
-            return HttpResponse::BadRequest().json(GetTaskListByProjectNameResponse { tasks: Vec::new() });
+            return HttpResponse::BadRequest().json(GetTaskListByProjectNameResponse { tasks: Vec::new(), dependencies: Vec::new() });
         }
     };
 
+    if project_ids.is_empty() {
+        return HttpResponse::Ok().json(GetTaskListByProjectNameResponse { tasks: Vec::new(), dependencies: Vec::new() });
+    }
+
     // Dynamically construct the IN clause
     let placeholders = project_ids.iter().map(|_| "?").collect::<Vec<_>>().join(",");
     let query_str = format!(
         "SELECT t.title AS task_title, 
-                u.user_name AS worker_name, 
+                COALESCE(u.user_name, '') AS worker_name, 
+                (SELECT GROUP_CONCAT(au.user_name ORDER BY au.user_name SEPARATOR ',') FROM TaskAssignees_ tas JOIN Users_ au ON tas.user_id = au.user_id WHERE tas.task_id = t.task_id) AS worker_names, 
                 t.start_time, 
                 t.end_time, 
                 t.description, 
+                t.status, 
+                t.progress, 
                 p.project_name, 
                 GROUP_CONCAT(DISTINCT ta.tag_color SEPARATOR ',') AS tag_colors
         FROM Tasks_ t
-        JOIN Users_ u ON t.worker_user_id = u.user_id
+        LEFT JOIN Users_ u ON t.worker_user_id = u.user_id
         JOIN Projects_ p ON t.project_id = p.project_id
         LEFT JOIN TagProjectMapping_ tpm ON t.project_id = tpm.project_id
         LEFT JOIN Tags_ ta ON tpm.tag_id = ta.tag_id AND ta.group_id = ?
@@ -905,59 +1597,81 @@ pub async fn get_task_list_by_project_name(
 
     // Execute the query with dynamically provided parameters
     let mut query = sqlx::query(&query_str).bind(group_id);
-    for project_id in project_ids {
+    for project_id in &project_ids {
         query = query.bind(project_id);
     }
 
     let tasks_result = query.fetch_all(pool.get_ref()).await;
     
-    match tasks_result {
+    let tasks: Vec<Task> = match tasks_result {
+        Ok(records) => records.into_iter().map(|record| Task {
+            task_title: record.get("task_title"),
+            worker_name: record.get("worker_name"),
+            worker_names: record.get::<Option<String>, _>("worker_names").map(|names| names.split(',').map(|s| s.to_string()).collect()).unwrap_or_default(),
+            start_time: record.get::<PrimitiveDateTime, _>("start_time").to_string(),
+            end_time: record.get::<PrimitiveDateTime, _>("end_time").to_string(),
+            description: record.get("description"),
+            project_name: record.get("project_name"),
+            tag_colors: record.get::<Option<String>, _>("tag_colors").unwrap_or_default().split(',').map(|s| s.to_string()).collect(),
+            status: record.get("status"),
+            progress: record.get("progress"),
+        }).filter(|task| status_filter.is_empty() || status_filter.contains(&task.status)).collect(),
+        Err(e) => {
+            error!("Failed to fetch tasks for group_id {}: {}", group_id, e);
+            return HttpResponse::InternalServerError().json(GetTaskListByProjectNameResponse { tasks: Vec::new(), dependencies: Vec::new() });
+        }
+    };
+
+    // Get dependency edges between tasks of the matched projects
+    let dependencies_query_str = format!(
+        "SELECT p.project_name, 
+                pt.title AS prev_task_title, 
+                nt.title AS next_task_title
+        FROM Dependencies_ d
+        JOIN Tasks_ pt ON d.prev_task_id = pt.task_id
+        JOIN Tasks_ nt ON d.next_task_id = nt.task_id
+        JOIN Projects_ p ON pt.project_id = p.project_id
+        WHERE p.project_id IN ({})", placeholders
+    );
+
+    let mut dependencies_query = sqlx::query(&dependencies_query_str);
+    for project_id in &project_ids {
+        dependencies_query = dependencies_query.bind(project_id);
+    }
+
+    let dependencies_result = dependencies_query.fetch_all(pool.get_ref()).await;
+
+    match dependencies_result {
         Ok(records) => {
-            let tasks: Vec<Task> = records.into_iter().map(|record| Task {
-                task_title: record.get("task_title"),
-                worker_name: record.get("worker_name"),
-                start_time: record.get::<PrimitiveDateTime, _>("start_time").to_string(),
-                end_time: record.get::<PrimitiveDateTime, _>("end_time").to_string(),
-                description: record.get("description"),
+            let dependencies: Vec<Dependency> = records.into_iter().map(|record| Dependency {
                 project_name: record.get("project_name"),
-                tag_colors: record.get::<Option<String>, _>("tag_colors").unwrap_or_default().split(',').map(|s| s.to_string()).collect(),
+                prev_task_title: record.get("prev_task_title"),
+                next_task_title: record.get("next_task_title"),
             }).collect();
 
-            HttpResponse::Ok().json(GetTaskListByTagListResponse { tasks })
+            HttpResponse::Ok().json(GetTaskListByProjectNameResponse { tasks, dependencies })
         },
         Err(e) => {
-            error!("Failed to fetch tasks for group_id {}: {}", group_id, e);
-            HttpResponse::InternalServerError().json(GetTaskListByTagListResponse { tasks: Vec::new() })
+            error!("Failed to fetch dependencies for group_id {}: {}", group_id, e);
+            HttpResponse::InternalServerError().json(GetTaskListByProjectNameResponse { tasks: Vec::new(), dependencies: Vec::new() })
         }
     }
 }
 
 pub async fn get_project_list(
     pool: web::Data<MySqlPool>,
-    _: HttpRequest,
+    current_user: CurrentUser,
     request: web::Json<GetProjectListRequest>,
 ) -> impl Responder {
     let owner_user_name = &request.owner_user_name;
     let group_name = &request.group_name;
 
-    // Get group_id using group_name and owner_user_name from Groups_
-    let group_id_result = sqlx::query!(
-        "
-        SELECT g.group_id 
-        FROM Groups_ g
-        JOIN Users_ u ON g.owner_user_id = u.user_id
-        WHERE g.group_name = ? AND u.user_name = ?
-        ",
-        group_name, owner_user_name
-    )
-    .fetch_one(pool.get_ref())
-    .await;
-
-    let group_id = match group_id_result {
-        Ok(record) => record.group_id,
-        Err(_) => { 
-            info!("Group not found: {}", group_name);
-            return HttpResponse::BadRequest().json(GetProjectListResponse { projects: Vec::new() });
+    // Check that the current user belongs to the group
+    let group_id = match require_group_member(pool.get_ref(), &current_user, owner_user_name, group_name).await {
+        Ok(membership) => membership.group_id,
+        Err(e) => {
+            info!("Group access denied for {} on {}: {}", current_user.user_name, group_name, e);
+            return HttpResponse::build(e.status_code()).json(GetProjectListResponse { projects: Vec::new() });
         }
     };
 
@@ -1033,3 +1747,105 @@ pub async fn get_project_list(
 
     HttpResponse::Ok().json(GetProjectListResponse { projects: projects_with_tags })
 }
+
+// Handler to report the workload of every member of the group over a date range
+pub async fn get_workload_report(
+    pool: web::Data<MySqlPool>,
+    current_user: CurrentUser,
+    request: web::Json<GetWorkloadReportRequest>,
+) -> impl Responder {
+    let owner_user_name = &request.owner_user_name;
+    let group_name = &request.group_name;
+
+    let format = format_description!("[year]-[month]-[day] [hour]:[minute]:[second]");
+    let (range_start, range_end) = match (
+        PrimitiveDateTime::parse(&request.start_time, &format),
+        PrimitiveDateTime::parse(&request.end_time, &format),
+    ) {
+        (Ok(start), Ok(end)) if start < end => (start, end),
+        _ => {
+            info!("Invalid report range: {} - {}", request.start_time, request.end_time);
+            return HttpResponse::BadRequest().json(GetWorkloadReportResponse { workers: Vec::new() });
+        }
+    };
+
+    // Check that the current user belongs to the group
+    let group_id = match require_group_member(pool.get_ref(), &current_user, owner_user_name, group_name).await {
+        Ok(membership) => membership.group_id,
+        Err(e) => {
+            info!("Group access denied for {} on {}: {}", current_user.user_name, group_name, e);
+            return HttpResponse::build(e.status_code()).json(GetWorkloadReportResponse { workers: Vec::new() });
+        }
+    };
+
+    let members_result = sqlx::query!(
+        "SELECT u.user_id, u.user_name
+         FROM GroupUserMapping_ gum
+         JOIN Users_ u ON gum.user_id = u.user_id
+         WHERE gum.group_id = ?
+         ORDER BY u.user_name",
+        group_id
+    )
+    .fetch_all(pool.get_ref())
+    .await;
+
+    let members = match members_result {
+        Ok(records) => records,
+        Err(e) => {
+            error!("Failed to fetch members for group_id {}: {}", group_id, e);
+            return HttpResponse::InternalServerError().json(GetWorkloadReportResponse { workers: Vec::new() });
+        }
+    };
+
+    // Tasks of the group overlapping the range, once per assignee
+    let tasks_result = sqlx::query!(
+        "SELECT tas.user_id AS worker_user_id, t.start_time, t.end_time
+         FROM Tasks_ t
+         JOIN TaskAssignees_ tas ON tas.task_id = t.task_id
+         JOIN Projects_ p ON t.project_id = p.project_id
+         WHERE p.group_id = ? AND t.start_time < ? AND t.end_time > ?",
+        group_id, range_end, range_start
+    )
+    .fetch_all(pool.get_ref())
+    .await;
+
+    let mut windows: HashMap<i32, Vec<(PrimitiveDateTime, PrimitiveDateTime)>> = HashMap::new();
+    match tasks_result {
+        Ok(records) => {
+            for record in records {
+                windows.entry(record.worker_user_id).or_default().push((record.start_time, record.end_time));
+            }
+        }
+        Err(e) => {
+            error!("Failed to fetch tasks for group_id {}: {}", group_id, e);
+            return HttpResponse::InternalServerError().json(GetWorkloadReportResponse { workers: Vec::new() });
+        }
+    }
+
+    let range_hours = hours(range_end - range_start);
+    let to_periods = |periods: Vec<(PrimitiveDateTime, PrimitiveDateTime)>| -> Vec<Period> {
+        periods.into_iter().map(|(start, end)| Period {
+            start_time: start.to_string(),
+            end_time: end.to_string(),
+        }).collect()
+    };
+
+    let workers: Vec<WorkerWorkload> = members.into_iter().map(|member| {
+        let workload = compute_workload(
+            range_start,
+            range_end,
+            windows.get(&member.user_id).map(Vec::as_slice).unwrap_or(&[]),
+        );
+        WorkerWorkload {
+            user_name: member.user_name,
+            task_count: workload.task_count,
+            assigned_hours: hours(workload.assigned),
+            busy_hours: hours(workload.busy),
+            utilization: hours(workload.busy) / range_hours,
+            overlaps: to_periods(workload.overlaps),
+            idle_periods: to_periods(workload.idle),
+        }
+    }).collect();
+
+    HttpResponse::Ok().json(GetWorkloadReportResponse { workers })
+}
\ No newline at end of file
@@ -1,10 +1,12 @@
 use serde::{Deserialize, Serialize};
+use crate::models::group_user_mapping::GroupRole;
 
 // structs
 #[derive(Serialize, Deserialize)]
 pub struct Worker {
     pub user_name: String,
     pub user_email: String,
+    pub role: String,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -16,12 +18,27 @@ pub struct Tag {
 #[derive(Serialize, Deserialize)]
 pub struct Task {
     pub task_title: String,
+    // first assignee, kept for older clients
     pub worker_name: String,
+    // every assignee of the task
+    pub worker_names: Vec<String>,
     pub start_time: String,
     pub end_time: String,
     pub description: String,
     pub project_name: String,
     pub tag_colors: Vec<String>,
+    // state of the group workflow
+    pub status: String,
+    // percent complete
+    pub progress: i32,
+}
+
+// Dependency edge between two tasks of the same project (prev must finish before next)
+#[derive(Serialize, Deserialize)]
+pub struct Dependency {
+    pub project_name: String,
+    pub prev_task_title: String,
+    pub next_task_title: String,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -47,7 +64,16 @@ pub struct GetWorkerListResponse {
 pub struct AddWorkerRequest {
     pub owner_user_name: String,
     pub group_name: String,
+    // the worker is invited by user name, or by email when the user name is empty
+    #[serde(default)]
     pub worker_user_name: String,
+    #[serde(default)]
+    pub worker_user_email: String,
+    // admin, editor or viewer; defaults to editor when writeable is set and viewer otherwise
+    #[serde(default)]
+    pub role: Option<GroupRole>,
+    #[serde(default)]
+    pub writeable: bool,
 }
 
 #[derive(Serialize)]
@@ -56,6 +82,106 @@ pub struct AddWorkerResponse {
     pub message: String,
 }
 
+#[derive(Deserialize)]
+pub struct AddInviteLinkRequest {
+    pub owner_user_name: String,
+    pub group_name: String,
+    // role given to users joining through the link, same defaults as AddWorkerRequest
+    #[serde(default)]
+    pub role: Option<GroupRole>,
+    #[serde(default)]
+    pub writeable: bool,
+    // no expiry / unlimited uses when missing
+    #[serde(default)]
+    pub expires_in_hours: Option<i64>,
+    #[serde(default)]
+    pub max_uses: Option<i32>,
+}
+
+#[derive(Serialize)]
+pub struct AddInviteLinkResponse {
+    pub success: bool,
+    pub message: String,
+    pub token: String,
+}
+
+#[derive(Deserialize)]
+pub struct GetInviteLinkListRequest {
+    pub owner_user_name: String,
+    pub group_name: String,
+}
+
+#[derive(Serialize)]
+pub struct InviteLink {
+    pub token: String,
+    pub role: String,
+    pub created_by: String,
+    pub created_at: String,
+    pub expires_at: Option<String>,
+    pub max_uses: Option<i32>,
+    pub use_count: i32,
+}
+
+#[derive(Serialize)]
+pub struct GetInviteLinkListResponse {
+    pub invite_links: Vec<InviteLink>,
+}
+
+#[derive(Deserialize)]
+pub struct DeleteInviteLinkRequest {
+    pub owner_user_name: String,
+    pub group_name: String,
+    pub token: String,
+}
+
+#[derive(Serialize)]
+pub struct DeleteInviteLinkResponse {
+    pub success: bool,
+    pub message: String,
+}
+
+#[derive(Deserialize)]
+pub struct RemoveWorkerRequest {
+    pub owner_user_name: String,
+    pub group_name: String,
+    pub worker_user_name: String,
+    // tasks of the removed worker go to this member, or are left unassigned when empty
+    #[serde(default)]
+    pub reassign_to_user_name: String,
+}
+
+#[derive(Serialize)]
+pub struct RemoveWorkerResponse {
+    pub success: bool,
+    pub message: String,
+}
+
+#[derive(Deserialize)]
+pub struct UpdateWorkerRoleRequest {
+    pub owner_user_name: String,
+    pub group_name: String,
+    pub worker_user_name: String,
+    pub new_role: GroupRole,
+}
+
+#[derive(Serialize)]
+pub struct UpdateWorkerRoleResponse {
+    pub success: bool,
+    pub message: String,
+}
+
+#[derive(Deserialize)]
+pub struct LeaveGroupRequest {
+    pub owner_user_name: String,
+    pub group_name: String,
+}
+
+#[derive(Serialize)]
+pub struct LeaveGroupResponse {
+    pub success: bool,
+    pub message: String,
+}
+
 
 #[derive(Deserialize)]
 pub struct GetTagListRequest {
@@ -119,6 +245,9 @@ pub struct GetTaskListByTagListRequest {
     pub owner_user_name: String,
     pub group_name: String,
     pub tags: Vec<String>,
+    // only tasks with one of these statuses, all tasks when empty
+    #[serde(default)]
+    pub status_filter: Vec<String>,
 }
 
 #[derive(Serialize)]
@@ -132,11 +261,15 @@ pub struct GetTaskListByProjectNameRequest {
     pub owner_user_name: String,
     pub group_name: String,
     pub project_name: String,
+    // only tasks with one of these statuses, all tasks when empty
+    #[serde(default)]
+    pub status_filter: Vec<String>,
 }
 
 #[derive(Serialize)]
 pub struct GetTaskListByProjectNameResponse {
     pub tasks: Vec<Task>,
+    pub dependencies: Vec<Dependency>,
 }
 
 #[derive(Deserialize)]
@@ -148,4 +281,73 @@ pub struct GetProjectListRequest {
 #[derive(Serialize)]
 pub struct GetProjectListResponse {
     pub projects: Vec<Project>
-}
\ No newline at end of file
+}
+
+#[derive(Deserialize)]
+pub struct GetWorkloadReportRequest {
+    pub owner_user_name: String,
+    pub group_name: String,
+    // "YYYY-MM-DD HH:MM:SS"
+    pub start_time: String,
+    pub end_time: String,
+}
+
+#[derive(Serialize)]
+pub struct Period {
+    pub start_time: String,
+    pub end_time: String,
+}
+
+#[derive(Serialize)]
+pub struct WorkerWorkload {
+    pub user_name: String,
+    pub task_count: usize,
+    // sum of the task durations inside the range, overlapping tasks counted twice
+    pub assigned_hours: f64,
+    // time covered by at least one task
+    pub busy_hours: f64,
+    // busy time over the length of the range
+    pub utilization: f64,
+    pub overlaps: Vec<Period>,
+    pub idle_periods: Vec<Period>,
+}
+
+#[derive(Serialize)]
+pub struct GetWorkloadReportResponse {
+    pub workers: Vec<WorkerWorkload>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct WorkflowTransition {
+    pub from_state: String,
+    pub to_state: String,
+}
+
+#[derive(Deserialize)]
+pub struct GetWorkflowRequest {
+    pub owner_user_name: String,
+    pub group_name: String,
+}
+
+#[derive(Serialize)]
+pub struct GetWorkflowResponse {
+    // ordered, the first state is given to new tasks
+    pub states: Vec<String>,
+    pub transitions: Vec<WorkflowTransition>,
+}
+
+#[derive(Deserialize)]
+pub struct UpdateWorkflowRequest {
+    pub owner_user_name: String,
+    pub group_name: String,
+    // ordered, empty to go back to the built-in workflow
+    pub states: Vec<String>,
+    #[serde(default)]
+    pub transitions: Vec<WorkflowTransition>,
+}
+
+#[derive(Serialize)]
+pub struct UpdateWorkflowResponse {
+    pub success: bool,
+    pub message: String,
+}
@@ -0,0 +1,145 @@
+use sqlx::MySqlPool;
+
+use crate::models::task::TaskStatus;
+
+// Ordered states of a group and the transitions allowed between them
+pub struct Workflow {
+    pub states: Vec<String>,
+    pub transitions: Vec<(String, String)>,
+}
+
+impl Workflow {
+    // Workflow of groups that have not defined their own: the built-in statuses, any transition allowed
+    pub fn builtin() -> Workflow {
+        let states: Vec<String> = TaskStatus::ALL.iter().map(|status| status.as_str().to_string()).collect();
+        let mut transitions = Vec::new();
+        for from in &states {
+            for to in &states {
+                if from != to {
+                    transitions.push((from.clone(), to.clone()));
+                }
+            }
+        }
+        Workflow { states, transitions }
+    }
+
+    pub fn has_state(&self, state: &str) -> bool {
+        self.states.iter().any(|s| s == state)
+    }
+
+    // State given to new tasks
+    pub fn initial_state(&self) -> &str {
+        &self.states[0]
+    }
+
+    // Staying in the same state is always allowed
+    pub fn allows(&self, from: &str, to: &str) -> bool {
+        from == to || self.transitions.iter().any(|(f, t)| f == from && t == to)
+    }
+
+    // Check that the workflow is well formed, returning the reason when it is not
+    pub fn validate(&self) -> Result<(), String> {
+        if self.states.is_empty() {
+            return Err("A workflow needs at least one state".to_string());
+        }
+        for (i, state) in self.states.iter().enumerate() {
+            if state.is_empty() || state.len() > 32 {
+                return Err("State names must be between 1 and 32 characters".to_string());
+            }
+            if self.states[..i].contains(state) {
+                return Err(format!("Duplicate state: {}", state));
+            }
+        }
+        for (i, (from, to)) in self.transitions.iter().enumerate() {
+            if !self.has_state(from) || !self.has_state(to) {
+                return Err(format!("Transition {} -> {} uses an unknown state", from, to));
+            }
+            if from == to {
+                return Err(format!("Transition {} -> {} does not change the state", from, to));
+            }
+            if self.transitions[..i].iter().any(|(f, t)| f == from && t == to) {
+                return Err(format!("Duplicate transition: {} -> {}", from, to));
+            }
+        }
+        Ok(())
+    }
+}
+
+// Load the workflow of a group, falling back to the built-in one
+pub async fn fetch_workflow(pool: &MySqlPool, group_id: i32) -> Result<Workflow, sqlx::Error> {
+    let states: Vec<String> = sqlx::query_scalar!(
+        "SELECT state_name FROM WorkflowStates_ WHERE group_id = ? ORDER BY position",
+        group_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    if states.is_empty() {
+        return Ok(Workflow::builtin());
+    }
+
+    let transitions = sqlx::query!(
+        "SELECT from_state, to_state FROM WorkflowTransitions_ WHERE group_id = ? ORDER BY transition_id",
+        group_id
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|record| (record.from_state, record.to_state))
+    .collect();
+
+    Ok(Workflow { states, transitions })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn workflow(states: &[&str], transitions: &[(&str, &str)]) -> Workflow {
+        Workflow {
+            states: states.iter().map(|state| state.to_string()).collect(),
+            transitions: transitions.iter().map(|(from, to)| (from.to_string(), to.to_string())).collect(),
+        }
+    }
+
+    #[test]
+    fn builtin_workflow_allows_every_move() {
+        let builtin = Workflow::builtin();
+        assert_eq!(builtin.validate(), Ok(()));
+        assert_eq!(builtin.initial_state(), "todo");
+        assert!(builtin.allows("done", "todo"));
+        assert!(builtin.allows("blocked", "in_progress"));
+    }
+
+    #[test]
+    fn only_listed_transitions_are_allowed() {
+        let review = workflow(&["open", "review", "closed"], &[("open", "review"), ("review", "closed"), ("review", "open")]);
+        assert_eq!(review.validate(), Ok(()));
+        assert_eq!(review.initial_state(), "open");
+        assert!(review.allows("open", "review"));
+        assert!(review.allows("review", "open"));
+        assert!(!review.allows("open", "closed"));
+        assert!(!review.allows("closed", "open"));
+    }
+
+    #[test]
+    fn staying_in_the_same_state_is_allowed() {
+        let review = workflow(&["open", "closed"], &[("open", "closed")]);
+        assert!(review.allows("closed", "closed"));
+        assert!(review.allows("open", "open"));
+    }
+
+    #[test]
+    fn validate_rejects_malformed_workflows() {
+        assert!(workflow(&[], &[]).validate().is_err());
+        assert!(workflow(&[""], &[]).validate().is_err());
+        assert!(workflow(&[&"x".repeat(33)], &[]).validate().is_err());
+        assert_eq!(workflow(&["open", "open"], &[]).validate(), Err("Duplicate state: open".to_string()));
+        assert!(workflow(&["open"], &[("open", "closed")]).validate().is_err());
+        assert!(workflow(&["open", "closed"], &[("open", "open")]).validate().is_err());
+        assert_eq!(
+            workflow(&["open", "closed"], &[("open", "closed"), ("open", "closed")]).validate(),
+            Err("Duplicate transition: open -> closed".to_string())
+        );
+    }
+}
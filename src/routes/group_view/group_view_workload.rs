@@ -0,0 +1,126 @@
+use time::{Duration, PrimitiveDateTime};
+
+// Workload of a single worker inside a reporting range
+pub struct Workload {
+    // Sum of the task durations, overlapping tasks counted twice
+    pub assigned: Duration,
+    // Time covered by at least one task
+    pub busy: Duration,
+    pub task_count: usize,
+    // Periods covered by two or more tasks
+    pub overlaps: Vec<(PrimitiveDateTime, PrimitiveDateTime)>,
+    // Periods covered by no task
+    pub idle: Vec<(PrimitiveDateTime, PrimitiveDateTime)>,
+}
+
+// Compute the workload of a worker from the windows of their tasks.
+// Windows are clipped to [range_start, range_end); windows outside the range are ignored.
+pub fn compute_workload(
+    range_start: PrimitiveDateTime,
+    range_end: PrimitiveDateTime,
+    windows: &[(PrimitiveDateTime, PrimitiveDateTime)],
+) -> Workload {
+    let clipped: Vec<(PrimitiveDateTime, PrimitiveDateTime)> = windows
+        .iter()
+        .map(|(start, end)| ((*start).max(range_start), (*end).min(range_end)))
+        .filter(|(start, end)| start < end)
+        .collect();
+
+    // +1 when a task starts, -1 when it ends; ends sort first so back-to-back tasks do not overlap
+    let mut events: Vec<(PrimitiveDateTime, i32)> = Vec::with_capacity(clipped.len() * 2);
+    for (start, end) in &clipped {
+        events.push((*start, 1));
+        events.push((*end, -1));
+    }
+    events.sort();
+
+    let mut busy = Duration::ZERO;
+    let mut overlaps = Vec::new();
+    let mut idle = Vec::new();
+
+    let mut active = 0;
+    let mut cursor = range_start;
+    for (time, delta) in events.into_iter().chain(std::iter::once((range_end, 0))) {
+        if time > cursor {
+            if active == 0 {
+                push_period(&mut idle, cursor, time);
+            } else {
+                busy += time - cursor;
+                if active >= 2 {
+                    push_period(&mut overlaps, cursor, time);
+                }
+            }
+            cursor = time;
+        }
+        active += delta;
+    }
+
+    Workload {
+        assigned: clipped.iter().map(|(start, end)| *end - *start).sum(),
+        busy,
+        task_count: clipped.len(),
+        overlaps,
+        idle,
+    }
+}
+
+// Append a period, merging it with the previous one when they touch
+fn push_period(
+    periods: &mut Vec<(PrimitiveDateTime, PrimitiveDateTime)>,
+    start: PrimitiveDateTime,
+    end: PrimitiveDateTime,
+) {
+    match periods.last_mut() {
+        Some((_, last_end)) if *last_end == start => *last_end = end,
+        _ => periods.push((start, end)),
+    }
+}
+
+pub fn hours(duration: Duration) -> f64 {
+    duration.as_seconds_f64() / 3600.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::datetime;
+
+    fn at(hour: i64) -> PrimitiveDateTime {
+        datetime!(2024-01-01 0:00) + Duration::hours(hour)
+    }
+
+    #[test]
+    fn touching_tasks_do_not_overlap() {
+        let workload = compute_workload(at(0), at(8), &[(at(1), at(3)), (at(3), at(5))]);
+        assert_eq!(workload.assigned, Duration::hours(4));
+        assert_eq!(workload.busy, Duration::hours(4));
+        assert!(workload.overlaps.is_empty());
+        assert_eq!(workload.idle, vec![(at(0), at(1)), (at(5), at(8))]);
+    }
+
+    #[test]
+    fn overlapping_tasks_are_counted_once_in_busy_time() {
+        let workload = compute_workload(at(0), at(8), &[(at(1), at(4)), (at(3), at(6)), (at(3), at(5))]);
+        assert_eq!(workload.task_count, 3);
+        assert_eq!(workload.assigned, Duration::hours(8));
+        assert_eq!(workload.busy, Duration::hours(5));
+        assert_eq!(workload.overlaps, vec![(at(3), at(5))]);
+        assert_eq!(workload.idle, vec![(at(0), at(1)), (at(6), at(8))]);
+    }
+
+    #[test]
+    fn tasks_are_clipped_to_the_range() {
+        let workload = compute_workload(at(2), at(6), &[(at(0), at(3)), (at(5), at(9)), (at(7), at(8))]);
+        assert_eq!(workload.task_count, 2);
+        assert_eq!(workload.assigned, Duration::hours(2));
+        assert_eq!(workload.idle, vec![(at(3), at(5))]);
+    }
+
+    #[test]
+    fn empty_range_is_all_idle() {
+        let workload = compute_workload(at(0), at(4), &[]);
+        assert_eq!(workload.busy, Duration::ZERO);
+        assert_eq!(workload.idle, vec![(at(0), at(4))]);
+        assert_eq!(hours(Duration::minutes(90)), 1.5);
+    }
+}
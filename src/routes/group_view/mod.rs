@@ -1,3 +1,4 @@
-
 pub mod group_view_handlers;
-pub mod group_view_models;
\ No newline at end of file
+pub mod group_view_models;
+pub mod group_view_workload;
+pub mod group_view_workflow;
\ No newline at end of file
@@ -1,10 +1,12 @@
-use actix_web::{web, HttpResponse, HttpRequest, Responder};
+use actix_web::{web, http::header, HttpResponse, HttpRequest, Responder};
 use sqlx::MySqlPool;
 use log::{error, info};
 use uuid::Uuid;
 use chrono::{Utc, Duration};
 use time::OffsetDateTime;
 use bcrypt::{hash, DEFAULT_COST, verify};
+use std::net::{IpAddr, SocketAddr};
+use crate::routes::auth::auth_extractors::CurrentUser;
 use super::login_models::{
     CheckUsernameRequest, CheckUsernameResponse,
     CheckEmailRequest, CheckEmailResponse,
@@ -12,8 +14,36 @@ use super::login_models::{
     LoginRequest, LoginResponse,
     AutoLoginRequest, AutoLoginResponse,
     LogoutRequest, LogoutResponse,
+    GetSessionListRequest, GetSessionListResponse, Session,
+    RevokeSessionRequest, RevokeSessionResponse,
+    RevokeOtherSessionsRequest, RevokeOtherSessionsResponse,
 };
 
+// Device metadata (user agent, client IP) stored alongside a new session
+fn client_metadata(req: &HttpRequest) -> (String, String) {
+    let user_agent: String = req
+        .headers()
+        .get(header::USER_AGENT)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or("")
+        .chars()
+        .take(512)
+        .collect();
+    // The address may come from Forwarded/X-Forwarded-For, so keep it only when it is one,
+    // with or without a port
+    let ip_address = req
+        .connection_info()
+        .realip_remote_addr()
+        .and_then(|addr| {
+            addr.parse::<IpAddr>()
+                .or_else(|_| addr.parse::<SocketAddr>().map(|socket_addr| socket_addr.ip()))
+                .ok()
+        })
+        .map(|ip| ip.to_string())
+        .unwrap_or_default();
+    (user_agent, ip_address)
+}
+
 pub async fn login_get() -> impl Responder {
     info!("Received request on /login_get endpoint");
     HttpResponse::Ok().body("Hello this is Flow'er's Login endpoint.")
@@ -136,6 +166,7 @@ pub async fn register(
 // login logic
 pub async fn login(
     pool: web::Data<MySqlPool>,
+    http_req: HttpRequest,
     req: web::Json<LoginRequest>,
 ) -> impl Responder {
     let username = &req.username;
@@ -144,7 +175,7 @@ pub async fn login(
 
     // 2. Get the user data from the database with username
     let result = sqlx::query!(
-        "SELECT user_id, password_hash FROM Users_ WHERE user_name = ?",
+        "SELECT user_id, password_hash, is_disabled AS `is_disabled: bool` FROM Users_ WHERE user_name = ?",
         username
     )
     .fetch_one(pool.get_ref())
@@ -181,6 +212,14 @@ pub async fn login(
         });
     }
 
+    if user.is_disabled {
+        info!("Disabled user tried to log in: {}", username);
+        return HttpResponse::Forbidden().json(LoginResponse {
+            success: false,
+            message: "Account is disabled".into(),
+        });
+    }
+
     // 4. Generate a new session ID
     let new_session_id = Uuid::new_v4().to_string();
     let expires_at = if req.remember_me {
@@ -189,72 +228,38 @@ pub async fn login(
         Utc::now() + Duration::minutes(30)
     };
 
-    // 5. Check if user already has a session
-    let session_check = sqlx::query!(
-        "SELECT session_id, expires_at FROM Sessions_ WHERE user_id = ?",
+    // 5. Clean up expired sessions of the user, other devices keep their sessions
+    let cleanup_result = sqlx::query!(
+        "DELETE FROM Sessions_ WHERE user_id = ? AND expires_at <= NOW()",
         user.user_id
     )
-    .fetch_optional(pool.get_ref())
+    .execute(pool.get_ref())
     .await;
 
-    match session_check {
-        Ok(Some(session)) => {
-            // Session exists, check if it has expired
-            if OffsetDateTime::now_utc() < session.expires_at {
-                info!("User {} already has an active session", username);
-                return HttpResponse::BadRequest().json(LoginResponse {
-                    success: false,
-                    message: "User already has an active session".into(),
-                });
-            } else {
-                // Update the expired session with a new session ID and expiration
-                let update_result = sqlx::query!(
-                    "UPDATE Sessions_ SET session_id = ?, expires_at = ?, is_persistent = ? WHERE user_id = ?",
-                    new_session_id,
-                    expires_at,
-                    req.remember_me,
-                    user.user_id
-                )
-                .execute(pool.get_ref())
-                .await;
+    if let Err(e) = cleanup_result {
+        error!("Failed to clean up expired sessions for user {}: {}", username, e);
+    }
 
-                if let Err(e) = update_result {
-                    error!("Failed to update session for user {}: {}", username, e);
-                    return HttpResponse::InternalServerError().json(LoginResponse {
-                        success: false,
-                        message: "Failed to update session".into(),
-                    });
-                }
-            }
-        }
-        Ok(None) => {
-            // No active session found, insert a new one
-            let insert_result = sqlx::query!(
-                "INSERT INTO Sessions_ (session_id, user_id, expires_at, is_persistent) VALUES (?, ?, ?, ?)",
-                new_session_id,
-                user.user_id,
-                expires_at,
-                req.remember_me
-            )
-            .execute(pool.get_ref())
-            .await;
+    let (user_agent, ip_address) = client_metadata(&http_req);
+    let insert_result = sqlx::query!(
+        "INSERT INTO Sessions_ (session_id, user_id, expires_at, is_persistent, user_agent, ip_address) VALUES (?, ?, ?, ?, ?, ?)",
+        new_session_id,
+        user.user_id,
+        expires_at,
+        req.remember_me,
+        user_agent,
+        ip_address
+    )
+    .execute(pool.get_ref())
+    .await;
 
-            if let Err(e) = insert_result {
-                error!("Failed to insert session for user {}: {}", username, e);
-                return HttpResponse::InternalServerError().json(LoginResponse {
-                    success: false,
-                    message: "Failed to create session".into(),
-                });
-            }
-        }
-        Err(e) => {
-            error!("Failed to query session for user {}: {}", username, e);
-            return HttpResponse::InternalServerError().json(LoginResponse {
-                success: false,
-                message: "Failed to check session".into(),
-            });
-        }
-    };
+    if let Err(e) = insert_result {
+        error!("Failed to insert session for user {}: {}", username, e);
+        return HttpResponse::InternalServerError().json(LoginResponse {
+            success: false,
+            message: "Failed to create session".into(),
+        });
+    }
 
     // 6. Return session ID inside a cookie to the client
     info!("User {} logged in successfully", username);
@@ -322,6 +327,18 @@ pub async fn auto_login(
                 });
             }
 
+            // Record the use of the session
+            let touch_result = sqlx::query!(
+                "UPDATE Sessions_ SET last_used_at = NOW() WHERE session_id = ?",
+                session_id
+            )
+            .execute(pool.get_ref())
+            .await;
+
+            if let Err(e) = touch_result {
+                error!("Failed to update last use of session ID {}: {}", session_id, e);
+            }
+
             // 3. If the session Id is valid, fetch the user association with the session
             let user_result = sqlx::query!(
                 "SELECT user_name FROM Users_ WHERE user_id = ?",
@@ -457,3 +474,131 @@ pub async fn logout(
         }
     }
 }
+
+// List the active sessions of the current user
+pub async fn get_session_list(
+    pool: web::Data<MySqlPool>,
+    current_user: CurrentUser,
+    _: web::Json<GetSessionListRequest>,
+) -> impl Responder {
+    let user_id = current_user.user_id;
+    let session_id = current_user.session_id;
+
+    let sessions_result = sqlx::query!(
+        "SELECT session_id, session_no, created_at, last_used_at, expires_at, user_agent, ip_address,
+                is_persistent AS `is_persistent: bool`
+         FROM Sessions_
+         WHERE user_id = ? AND expires_at > NOW()
+         ORDER BY session_no",
+        user_id
+    )
+    .fetch_all(pool.get_ref())
+    .await;
+
+    match sessions_result {
+        Ok(records) => {
+            let sessions: Vec<Session> = records.into_iter().map(|record| Session {
+                session_no: record.session_no,
+                created_at: record.created_at.to_string(),
+                last_used_at: record.last_used_at.to_string(),
+                expires_at: record.expires_at.to_string(),
+                user_agent: record.user_agent,
+                ip_address: record.ip_address,
+                is_persistent: record.is_persistent.unwrap_or(false),
+                is_current: record.session_id == session_id,
+            }).collect();
+
+            HttpResponse::Ok().json(GetSessionListResponse {
+                success: true,
+                message: "Session list fetched successfully".into(),
+                sessions,
+            })
+        }
+        Err(e) => {
+            error!("Failed to fetch sessions for user_id {}: {}", user_id, e);
+            HttpResponse::InternalServerError().json(GetSessionListResponse {
+                success: false,
+                message: "Failed to fetch sessions".into(),
+                sessions: Vec::new(),
+            })
+        }
+    }
+}
+
+// Revoke one of the sessions of the current user
+pub async fn revoke_session(
+    pool: web::Data<MySqlPool>,
+    current_user: CurrentUser,
+    request: web::Json<RevokeSessionRequest>,
+) -> impl Responder {
+    let session_no = request.session_no;
+    let user_id = current_user.user_id;
+
+    // Only sessions of the current user can be revoked
+    let delete_result = sqlx::query!(
+        "DELETE FROM Sessions_ WHERE session_no = ? AND user_id = ?",
+        session_no, user_id
+    )
+    .execute(pool.get_ref())
+    .await;
+
+    match delete_result {
+        Ok(result) if result.rows_affected() == 0 => {
+            info!("Session {} not found for user_id {}", session_no, user_id);
+            HttpResponse::BadRequest().json(RevokeSessionResponse {
+                success: false,
+                message: "Session not found".into(),
+            })
+        }
+        Ok(_) => {
+            info!("Session {} revoked for user_id {}", session_no, user_id);
+            HttpResponse::Ok().json(RevokeSessionResponse {
+                success: true,
+                message: "Session revoked successfully".into(),
+            })
+        }
+        Err(e) => {
+            error!("Failed to revoke session {} for user_id {}: {}", session_no, user_id, e);
+            HttpResponse::InternalServerError().json(RevokeSessionResponse {
+                success: false,
+                message: "Failed to revoke session".into(),
+            })
+        }
+    }
+}
+
+// Revoke every session of the current user except the one making the request
+pub async fn revoke_other_sessions(
+    pool: web::Data<MySqlPool>,
+    current_user: CurrentUser,
+    _: web::Json<RevokeOtherSessionsRequest>,
+) -> impl Responder {
+    let user_id = current_user.user_id;
+    let session_id = current_user.session_id;
+
+    let delete_result = sqlx::query!(
+        "DELETE FROM Sessions_ WHERE user_id = ? AND session_id != ?",
+        user_id, session_id
+    )
+    .execute(pool.get_ref())
+    .await;
+
+    match delete_result {
+        Ok(result) => {
+            info!("Revoked {} other sessions for user_id {}", result.rows_affected(), user_id);
+            HttpResponse::Ok().json(RevokeOtherSessionsResponse {
+                success: true,
+                message: "Other sessions revoked successfully".into(),
+                revoked_count: result.rows_affected(),
+            })
+        }
+        Err(e) => {
+            error!("Failed to revoke other sessions for user_id {}: {}", user_id, e);
+            HttpResponse::InternalServerError().json(RevokeOtherSessionsResponse {
+                success: false,
+                message: "Failed to revoke other sessions".into(),
+                revoked_count: 0,
+            })
+        }
+    }
+}
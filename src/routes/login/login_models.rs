@@ -77,3 +77,54 @@ pub struct LogoutResponse {
     pub success: bool,
     pub message: String,
 }
+
+
+// Session list request and response
+#[derive(Deserialize)]
+pub struct GetSessionListRequest {
+}
+
+#[derive(Serialize)]
+pub struct Session {
+    pub session_no: i32,
+    pub created_at: String,
+    pub last_used_at: String,
+    pub expires_at: String,
+    pub user_agent: String,
+    pub ip_address: String,
+    pub is_persistent: bool,
+    pub is_current: bool,
+}
+
+#[derive(Serialize)]
+pub struct GetSessionListResponse {
+    pub success: bool,
+    pub message: String,
+    pub sessions: Vec<Session>,
+}
+
+
+// Revoke session request and response
+#[derive(Deserialize)]
+pub struct RevokeSessionRequest {
+    pub session_no: i32,
+}
+
+#[derive(Serialize)]
+pub struct RevokeSessionResponse {
+    pub success: bool,
+    pub message: String,
+}
+
+
+// Revoke other sessions request and response
+#[derive(Deserialize)]
+pub struct RevokeOtherSessionsRequest {
+}
+
+#[derive(Serialize)]
+pub struct RevokeOtherSessionsResponse {
+    pub success: bool,
+    pub message: String,
+    pub revoked_count: u64,
+}
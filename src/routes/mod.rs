@@ -1,5 +1,7 @@
 
 pub mod admin;
+pub mod auth;
+pub mod notification;
 
 pub mod login;
 pub mod group_selection;
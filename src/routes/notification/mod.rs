@@ -0,0 +1,3 @@
+pub mod notification_handlers;
+pub mod notification_helpers;
+pub mod notification_models;
\ No newline at end of file
@@ -0,0 +1,158 @@
+use actix_web::{web, HttpResponse, Responder};
+use sqlx::MySqlPool;
+use log::{error, info};
+use crate::routes::auth::auth_extractors::CurrentUser;
+use super::notification_models::{
+    GetNotificationListRequest, GetNotificationListResponse, Notification,
+    MarkReadRequest, MarkReadResponse,
+    MarkAllReadRequest, MarkAllReadResponse,
+};
+
+// Notifications returned per page when the request gives no limit, and the largest page allowed
+const NOTIFICATION_PAGE_SIZE: u32 = 50;
+const MAX_NOTIFICATION_PAGE_SIZE: u32 = 100;
+
+// Default handler for notification root
+pub async fn notification_get() -> impl Responder {
+    HttpResponse::Ok().body("Hello, this is the Notification endpoint.")
+}
+
+// Handler to get the notifications of the current user, newest first
+pub async fn get_notification_list(
+    pool: web::Data<MySqlPool>,
+    current_user: CurrentUser,
+    request: web::Json<GetNotificationListRequest>,
+) -> impl Responder {
+    let include_read = request.include_read;
+    let offset = request.offset;
+    let limit = request.limit.unwrap_or(NOTIFICATION_PAGE_SIZE).clamp(1, MAX_NOTIFICATION_PAGE_SIZE);
+
+    let unread_count = match sqlx::query!(
+        "SELECT COUNT(*) AS count FROM Notifications_ WHERE user_id = ? AND is_read = FALSE",
+        current_user.user_id
+    )
+    .fetch_one(pool.get_ref())
+    .await
+    {
+        Ok(record) => record.count,
+        Err(e) => {
+            error!("Failed to count notifications of user {}: {}", current_user.user_id, e);
+            return HttpResponse::InternalServerError().json(GetNotificationListResponse { notifications: Vec::new(), unread_count: 0 });
+        }
+    };
+
+    let notifications_result = sqlx::query!(
+        "SELECT n.notification_id, n.kind, n.message, n.is_read AS `is_read: bool`, n.created_at,
+                o.user_name AS `owner_user_name?`, g.group_name AS `group_name?`,
+                p.project_name AS `project_name?`, t.title AS `task_title?`
+         FROM Notifications_ n
+         LEFT JOIN Groups_ g ON n.group_id = g.group_id
+         LEFT JOIN Users_ o ON g.owner_user_id = o.user_id
+         LEFT JOIN Tasks_ t ON n.task_id = t.task_id
+         LEFT JOIN Projects_ p ON t.project_id = p.project_id
+         WHERE n.user_id = ? AND (? OR n.is_read = FALSE)
+         ORDER BY n.created_at DESC, n.notification_id DESC
+         LIMIT ? OFFSET ?",
+        current_user.user_id, include_read, limit, offset
+    )
+    .fetch_all(pool.get_ref())
+    .await;
+
+    match notifications_result {
+        Ok(records) => {
+            let notifications: Vec<Notification> = records.into_iter().map(|record| Notification {
+                notification_id: record.notification_id,
+                kind: record.kind,
+                message: record.message,
+                owner_user_name: record.owner_user_name,
+                group_name: record.group_name,
+                project_name: record.project_name,
+                task_title: record.task_title,
+                is_read: record.is_read,
+                created_at: record.created_at.to_string(),
+            }).collect();
+
+            HttpResponse::Ok().json(GetNotificationListResponse { notifications, unread_count })
+        }
+        Err(e) => {
+            error!("Failed to fetch notifications of user {}: {}", current_user.user_id, e);
+            HttpResponse::InternalServerError().json(GetNotificationListResponse { notifications: Vec::new(), unread_count: 0 })
+        }
+    }
+}
+
+// Handler to mark some notifications of the current user as read
+pub async fn mark_read(
+    pool: web::Data<MySqlPool>,
+    current_user: CurrentUser,
+    request: web::Json<MarkReadRequest>,
+) -> impl Responder {
+    let notification_ids = &request.notification_ids;
+
+    if notification_ids.is_empty() {
+        return HttpResponse::BadRequest().json(MarkReadResponse {
+            success: false,
+            message: "No notifications given".to_string(),
+        });
+    }
+
+    // Dynamically construct the IN clause; notifications of other users are left untouched
+    let placeholders = notification_ids.iter().map(|_| "?").collect::<Vec<_>>().join(",");
+    let query_str = format!(
+        "UPDATE Notifications_ SET is_read = TRUE WHERE user_id = ? AND notification_id IN ({})",
+        placeholders
+    );
+
+    let mut query = sqlx::query(&query_str).bind(current_user.user_id);
+    for notification_id in notification_ids {
+        query = query.bind(notification_id);
+    }
+
+    match query.execute(pool.get_ref()).await {
+        Ok(result) => {
+            info!("{} marked {} notifications as read", current_user.user_name, result.rows_affected());
+            HttpResponse::Ok().json(MarkReadResponse {
+                success: true,
+                message: "Notifications marked as read".to_string(),
+            })
+        }
+        Err(e) => {
+            error!("Failed to mark notifications of user {} as read: {}", current_user.user_id, e);
+            HttpResponse::InternalServerError().json(MarkReadResponse {
+                success: false,
+                message: "Failed to mark notifications as read".to_string(),
+            })
+        }
+    }
+}
+
+// Handler to mark every notification of the current user as read
+pub async fn mark_all_read(
+    pool: web::Data<MySqlPool>,
+    current_user: CurrentUser,
+    _: web::Json<MarkAllReadRequest>,
+) -> impl Responder {
+    let update_result = sqlx::query!(
+        "UPDATE Notifications_ SET is_read = TRUE WHERE user_id = ? AND is_read = FALSE",
+        current_user.user_id
+    )
+    .execute(pool.get_ref())
+    .await;
+
+    match update_result {
+        Ok(result) => {
+            info!("{} marked {} notifications as read", current_user.user_name, result.rows_affected());
+            HttpResponse::Ok().json(MarkAllReadResponse {
+                success: true,
+                message: "All notifications marked as read".to_string(),
+            })
+        }
+        Err(e) => {
+            error!("Failed to mark notifications of user {} as read: {}", current_user.user_id, e);
+            HttpResponse::InternalServerError().json(MarkAllReadResponse {
+                success: false,
+                message: "Failed to mark notifications as read".to_string(),
+            })
+        }
+    }
+}
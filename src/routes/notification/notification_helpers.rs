@@ -0,0 +1,51 @@
+use sqlx::MySqlPool;
+use log::error;
+use crate::models::notification::NotificationKind;
+
+// Length of Notifications_.message, in characters
+const MAX_MESSAGE_CHARS: usize = 512;
+
+// Queue an in-app notification for a user; works on the pool or inside a transaction
+pub async fn create_notification<'e, E>(
+    executor: E,
+    user_id: i32,
+    kind: NotificationKind,
+    message: &str,
+    group_id: Option<i32>,
+    task_id: Option<i32>,
+) -> Result<(), sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::MySql>,
+{
+    // Messages quote user names and task titles, so cut them to fit rather than fail the insert
+    let message: String = if message.chars().count() > MAX_MESSAGE_CHARS {
+        message.chars().take(MAX_MESSAGE_CHARS - 3).chain("...".chars()).collect()
+    } else {
+        message.to_string()
+    };
+
+    sqlx::query!(
+        "INSERT INTO Notifications_ (user_id, kind, message, group_id, task_id) VALUES (?, ?, ?, ?, ?)",
+        user_id, kind.as_str(), message, group_id, task_id
+    )
+    .execute(executor)
+    .await?;
+
+    Ok(())
+}
+
+// Notify several users, logging failures instead of failing the request that triggered them
+pub async fn notify_users(
+    pool: &MySqlPool,
+    user_ids: &[i32],
+    kind: NotificationKind,
+    message: &str,
+    group_id: Option<i32>,
+    task_id: Option<i32>,
+) {
+    for user_id in user_ids {
+        if let Err(e) = create_notification(pool, *user_id, kind, message, group_id, task_id).await {
+            error!("Failed to notify user {} ({}): {}", user_id, kind.as_str(), e);
+        }
+    }
+}
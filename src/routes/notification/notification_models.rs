@@ -0,0 +1,56 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize)]
+pub struct Notification {
+    pub notification_id: i32,
+    // mention, task_assigned, task_rescheduled or group_invitation
+    pub kind: String,
+    pub message: String,
+    // group and task the notification is about, missing when not relevant or deleted since
+    pub owner_user_name: Option<String>,
+    pub group_name: Option<String>,
+    pub project_name: Option<String>,
+    pub task_title: Option<String>,
+    pub is_read: bool,
+    pub created_at: String,
+}
+
+#[derive(Deserialize)]
+pub struct GetNotificationListRequest {
+    // unread notifications only unless set
+    #[serde(default)]
+    pub include_read: bool,
+    // newest notifications first, `limit` notifications after skipping `offset`
+    #[serde(default)]
+    pub offset: u32,
+    #[serde(default)]
+    pub limit: Option<u32>,
+}
+
+#[derive(Serialize)]
+pub struct GetNotificationListResponse {
+    pub notifications: Vec<Notification>,
+    pub unread_count: i64,
+}
+
+#[derive(Deserialize)]
+pub struct MarkReadRequest {
+    pub notification_ids: Vec<i32>,
+}
+
+#[derive(Serialize)]
+pub struct MarkReadResponse {
+    pub success: bool,
+    pub message: String,
+}
+
+#[derive(Deserialize)]
+pub struct MarkAllReadRequest {
+    // if needed
+}
+
+#[derive(Serialize)]
+pub struct MarkAllReadResponse {
+    pub success: bool,
+    pub message: String,
+}
\ No newline at end of file
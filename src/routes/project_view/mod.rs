@@ -1,3 +1,7 @@
 
 pub mod project_view_handlers;
-pub mod project_view_models;
\ No newline at end of file
+pub mod project_view_models;
+pub mod project_view_schedule;
+pub mod project_view_hierarchy;
+pub mod project_view_mentions;
+pub mod project_view_storage;
\ No newline at end of file
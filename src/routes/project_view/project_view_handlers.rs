@@ -1,18 +1,343 @@
-use actix_web::{web, HttpResponse, HttpRequest, Responder};
+use actix_web::{web, HttpResponse, Responder};
+use actix_web::http::header::{ContentDisposition, DispositionParam, DispositionType};
+use actix_multipart::{Field, Multipart};
+use futures_util::StreamExt;
+use tokio::io::AsyncWriteExt;
+use tokio_util::io::ReaderStream;
+use uuid::Uuid;
+use std::collections::HashMap;
 use sqlx::MySqlPool;
 use log::{error, info};
 use time::{PrimitiveDateTime, macros::format_description};
+use crate::models::group::ConflictMode;
+use crate::routes::group_view::group_view_workflow::fetch_workflow;
+use crate::models::group_user_mapping::Permission;
+use crate::models::notification::NotificationKind;
+use crate::routes::notification::notification_helpers::{create_notification, notify_users};
+use crate::routes::auth::auth_extractors::{CurrentUser, require_group_member};
+use super::project_view_schedule::{
+    TaskWindow, find_cycle, find_schedule_conflicts, task_id_by_title,
+    describe_cycle, describe_conflicts, compute_critical_path, cascade_reschedule,
+};
+use super::project_view_mentions::parse_mentions;
+use super::project_view_storage::{attachment_dir, attachment_path, clean_file_name, remove_attachment_files};
+use super::project_view_hierarchy::{TaskProgress, children_of, subtree, roll_up};
 use super::project_view_models::{
     GetProjectDetailRequest, GetProjectDetailResponse,
     AddProjectRequest, AddProjectResponse,
     UpdateProjectRequest, UpdateProjectResponse,
     DeleteProjectRequest, DeleteProjectResponse,
-    GetTaskDetailRequest, GetTaskDetailResponse, Task,
+    GetTaskDetailRequest, GetTaskDetailResponse, Task, TaskNode, Dependency,
     AddTaskRequest, AddTaskResponse,
-    UpdateTaskRequest, UpdateTaskResponse,
+    UpdateTaskRequest, UpdateTaskResponse, MovedTask, WorkerConflict,
     DeleteTaskRequest, DeleteTaskResponse,
+    GetDependencyListRequest, GetDependencyListResponse,
+    AddDependencyRequest, AddDependencyResponse,
+    DeleteDependencyRequest, DeleteDependencyResponse,
+    GetCriticalPathRequest, GetCriticalPathResponse, TaskSchedule,
+    GetCommentListRequest, GetCommentListResponse, Comment,
+    AddCommentRequest, AddCommentResponse,
+    UpdateCommentRequest, UpdateCommentResponse,
+    DeleteCommentRequest, DeleteCommentResponse,
+    UploadAttachmentResponse,
+    GetAttachmentListRequest, GetAttachmentListResponse, Attachment,
+    DownloadAttachmentRequest, DownloadAttachmentResponse,
+    DeleteAttachmentRequest, DeleteAttachmentResponse,
 };
 
+// Comments returned per page when the request gives no limit, and the largest page allowed
+const COMMENT_PAGE_SIZE: u32 = 20;
+const MAX_COMMENT_PAGE_SIZE: u32 = 100;
+
+// Longest text field accepted in an attachment upload
+const MAX_UPLOAD_FIELD_BYTES: usize = 1024;
+
+// Length of Attachments_.content_type; media types are ASCII
+const MAX_CONTENT_TYPE_BYTES: usize = 255;
+
+// Lock the project row until the end of the transaction, so changes to its dependency graph
+// are validated one at a time against everything committed before them
+async fn lock_project_schedule(
+    tx: &mut sqlx::Transaction<'_, sqlx::MySql>,
+    project_id: i32,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!("SELECT project_id FROM Projects_ WHERE project_id = ? FOR UPDATE", project_id)
+        .fetch_one(&mut **tx)
+        .await?;
+    Ok(())
+}
+
+// Load the scheduled window of every task in the project together with its dependency edges
+async fn fetch_project_schedule(
+    conn: &mut sqlx::MySqlConnection,
+    project_id: i32,
+) -> Result<(HashMap<i32, TaskWindow>, Vec<(i32, i32)>), sqlx::Error> {
+    let task_records = sqlx::query!(
+        "
+        SELECT t.task_id, t.title, t.start_time, t.end_time
+        FROM Tasks_ t
+        WHERE t.project_id = ?
+        ",
+        project_id
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let tasks = task_records.into_iter().map(|record| (record.task_id, TaskWindow {
+        title: record.title,
+        start_time: record.start_time,
+        end_time: record.end_time,
+    })).collect();
+
+    let edge_records = sqlx::query!(
+        "
+        SELECT d.prev_task_id, d.next_task_id
+        FROM Dependencies_ d
+        JOIN Tasks_ t ON d.prev_task_id = t.task_id
+        WHERE t.project_id = ?
+        ",
+        project_id
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let edges = edge_records.into_iter().map(|record| (record.prev_task_id, record.next_task_id)).collect();
+
+    Ok((tasks, edges))
+}
+
+// Find the tasks of the assignees, in any project, overlapping the given window.
+// Tasks in `excluded_task_ids` are skipped, such as the task itself or tasks about to be moved.
+// Tasks in groups the viewer does not belong to are reported without their group, project or title.
+async fn fetch_worker_conflicts(
+    pool: &MySqlPool,
+    viewer_user_id: i32,
+    worker_user_ids: &[i32],
+    start_time: PrimitiveDateTime,
+    end_time: PrimitiveDateTime,
+    excluded_task_ids: &[i32],
+) -> Result<Vec<WorkerConflict>, sqlx::Error> {
+    let mut worker_conflicts = Vec::new();
+    for worker_user_id in worker_user_ids {
+        let records = sqlx::query!(
+            "
+            SELECT t.task_id, u.user_name, g.group_name, p.project_name, t.title, t.start_time, t.end_time,
+                   EXISTS (
+                       SELECT 1 FROM GroupUserMapping_ m
+                       WHERE m.group_id = g.group_id AND m.user_id = ?
+                   ) AS `visible: bool`
+            FROM TaskAssignees_ tas
+            JOIN Users_ u ON tas.user_id = u.user_id
+            JOIN Tasks_ t ON tas.task_id = t.task_id
+            JOIN Projects_ p ON t.project_id = p.project_id
+            JOIN Groups_ g ON p.group_id = g.group_id
+            WHERE tas.user_id = ? AND t.start_time < ? AND t.end_time > ?
+            ORDER BY t.start_time
+            ",
+            viewer_user_id, worker_user_id, end_time, start_time
+        )
+        .fetch_all(pool)
+        .await?;
+
+        let records = records.into_iter().filter(|record| !excluded_task_ids.contains(&record.task_id));
+        worker_conflicts.extend(records.map(|record| {
+            let visible = record.visible;
+            WorkerConflict {
+                worker_name: record.user_name,
+                group_name: visible.then_some(record.group_name),
+                project_name: visible.then_some(record.project_name),
+                task_title: visible.then_some(record.title),
+                start_time: record.start_time.to_string(),
+                end_time: record.end_time.to_string(),
+            }
+        }));
+    }
+
+    Ok(worker_conflicts)
+}
+
+fn describe_worker_conflicts(worker_conflicts: &[WorkerConflict]) -> String {
+    let details: Vec<String> = worker_conflicts
+        .iter()
+        .map(|conflict| match (&conflict.task_title, &conflict.group_name, &conflict.project_name) {
+            (Some(task_title), Some(group_name), Some(project_name)) => {
+                format!("{} on '{}' in {}/{}", conflict.worker_name, task_title, group_name, project_name)
+            }
+            _ => format!("{} on a task in another group", conflict.worker_name),
+        })
+        .collect();
+    format!("Workers are already booked on overlapping tasks: {}", details.join(", "))
+}
+
+// Worker conflicts of the tasks moved by a cascade, using the assignees of each moved task.
+// The updated task and the moved tasks are checked against their new windows rather than the stored ones.
+async fn fetch_cascade_worker_conflicts(
+    pool: &MySqlPool,
+    viewer_user_id: i32,
+    updated_task: (i32, &TaskWindow, &[i32]),
+    moved_tasks: &[(i32, TaskWindow)],
+    group_name: &str,
+    project_name: &str,
+) -> Result<Vec<WorkerConflict>, sqlx::Error> {
+    let (updated_task_id, updated_window, updated_worker_user_ids) = updated_task;
+    let mut rewritten_task_ids = vec![updated_task_id];
+    rewritten_task_ids.extend(moved_tasks.iter().map(|(moved_task_id, _)| *moved_task_id));
+
+    let mut checked: Vec<(&TaskWindow, Vec<i32>)> = vec![(updated_window, updated_worker_user_ids.to_vec())];
+    let mut worker_conflicts = Vec::new();
+    for (moved_task_id, moved_task) in moved_tasks {
+        let assignees = sqlx::query!(
+            "
+            SELECT u.user_id, u.user_name
+            FROM TaskAssignees_ tas
+            JOIN Users_ u ON tas.user_id = u.user_id
+            WHERE tas.task_id = ?
+            ",
+            moved_task_id
+        )
+        .fetch_all(pool)
+        .await?;
+        let worker_user_ids: Vec<i32> = assignees.iter().map(|assignee| assignee.user_id).collect();
+
+        worker_conflicts.extend(
+            fetch_worker_conflicts(pool, viewer_user_id, &worker_user_ids, moved_task.start_time, moved_task.end_time, &rewritten_task_ids).await?
+        );
+
+        for (other_task, other_worker_user_ids) in &checked {
+            if other_task.start_time >= moved_task.end_time || other_task.end_time <= moved_task.start_time {
+                continue;
+            }
+            for assignee in assignees.iter().filter(|assignee| other_worker_user_ids.contains(&assignee.user_id)) {
+                worker_conflicts.push(WorkerConflict {
+                    worker_name: assignee.user_name.clone(),
+                    group_name: Some(group_name.to_string()),
+                    project_name: Some(project_name.to_string()),
+                    task_title: Some(other_task.title.clone()),
+                    start_time: other_task.start_time.to_string(),
+                    end_time: other_task.end_time.to_string(),
+                });
+            }
+        }
+        checked.push((moved_task, worker_user_ids));
+    }
+
+    Ok(worker_conflicts)
+}
+
+// Resolve worker names to user ids, dropping repeated names.
+// Only members of the group can be assigned; returns the first other name on failure.
+async fn find_worker_ids(pool: &MySqlPool, group_id: i32, worker_names: &[String]) -> Result<Vec<i32>, String> {
+    let mut worker_user_ids = Vec::new();
+    for worker_name in worker_names {
+        let worker_id_result = sqlx::query!(
+            "
+            SELECT u.user_id 
+            FROM Users_ u
+            JOIN GroupUserMapping_ m ON u.user_id = m.user_id
+            WHERE m.group_id = ? AND u.user_name = ?
+            ",
+            group_id, worker_name
+        )
+        .fetch_one(pool)
+        .await;
+
+        match worker_id_result {
+            Ok(record) if !worker_user_ids.contains(&record.user_id) => worker_user_ids.push(record.user_id),
+            Ok(_) => {}
+            Err(_) => return Err(worker_name.clone()),
+        }
+    }
+    Ok(worker_user_ids)
+}
+
+// Replace the assignees of a task in TaskAssignees_
+async fn replace_assignees(
+    tx: &mut sqlx::Transaction<'_, sqlx::MySql>,
+    task_id: i32,
+    worker_user_ids: &[i32],
+) -> Result<(), sqlx::Error> {
+    sqlx::query!("DELETE FROM TaskAssignees_ WHERE task_id = ?", task_id)
+        .execute(&mut **tx)
+        .await?;
+
+    for worker_user_id in worker_user_ids {
+        sqlx::query!(
+            "INSERT INTO TaskAssignees_ (task_id, user_id) VALUES (?, ?)",
+            task_id, worker_user_id
+        )
+        .execute(&mut **tx)
+        .await?;
+    }
+
+    Ok(())
+}
+
+// Parent of every task in the project, ordered by start time
+async fn fetch_task_parents<'e, E>(executor: E, project_id: i32) -> Result<Vec<(i32, Option<i32>)>, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::MySql>,
+{
+    let records = sqlx::query!(
+        "SELECT task_id, parent_task_id FROM Tasks_ WHERE project_id = ? ORDER BY start_time, task_id",
+        project_id
+    )
+    .fetch_all(executor)
+    .await?;
+
+    Ok(records.into_iter().map(|record| (record.task_id, record.parent_task_id)).collect())
+}
+
+// Nest the tasks under their parents.
+// A task is kept when it passes the status filter or one of its subtasks does.
+fn build_task_tree(
+    parent_task_id: Option<i32>,
+    children: &HashMap<Option<i32>, Vec<i32>>,
+    tasks: &mut HashMap<i32, Task>,
+    status_filter: &[String],
+) -> Vec<TaskNode> {
+    let mut nodes = Vec::new();
+    for task_id in children.get(&parent_task_id).cloned().unwrap_or_default() {
+        let task = match tasks.remove(&task_id) {
+            Some(task) => task,
+            None => continue,
+        };
+        let subtasks = build_task_tree(Some(task_id), children, tasks, status_filter);
+        if status_filter.is_empty() || status_filter.contains(&task.status) || !subtasks.is_empty() {
+            nodes.push(TaskNode { task, subtasks });
+        }
+    }
+    nodes
+}
+
+// Tell the assignees of a rescheduled task about its new window.
+// The member making the change and the users in `skip` are not notified.
+async fn notify_rescheduled(
+    pool: &MySqlPool,
+    current_user: &CurrentUser,
+    group_id: i32,
+    task_id: i32,
+    task: &TaskWindow,
+    skip: &[i32],
+) {
+    let assignees = match sqlx::query_scalar!("SELECT user_id FROM TaskAssignees_ WHERE task_id = ?", task_id)
+        .fetch_all(pool)
+        .await
+    {
+        Ok(assignees) => assignees,
+        Err(e) => {
+            error!("Failed to fetch assignees of task {}: {}", task_id, e);
+            return;
+        }
+    };
+
+    let recipients: Vec<i32> = assignees
+        .into_iter()
+        .filter(|user_id| *user_id != current_user.user_id && !skip.contains(user_id))
+        .collect();
+    let message = format!("{} rescheduled task '{}' to {} - {}", current_user.user_name, task.title, task.start_time, task.end_time);
+    notify_users(pool, &recipients, NotificationKind::TaskRescheduled, &message, Some(group_id), Some(task_id)).await;
+}
+
 // Default handler for project selection root
 pub async fn project_view_get() -> impl Responder {
     HttpResponse::Ok().body("Hello, this is the Project View endpoint.")
@@ -21,31 +346,19 @@ pub async fn project_view_get() -> impl Responder {
 // Handler to get project details
 pub async fn get_project_detail(
     pool: web::Data<MySqlPool>,
-    _: HttpRequest,
+    current_user: CurrentUser,
     request: web::Json<GetProjectDetailRequest>,
 ) -> impl Responder {
     let owner_user_name = &request.owner_user_name;
     let group_name = &request.group_name;
     let project_name = &request.project_name;
 
-    // Get group_id using group_name from Groups_
-    let group_id_result = sqlx::query!(
-        "
-        SELECT g.group_id 
-        FROM Groups_ g
-        JOIN Users_ u ON g.owner_user_id = u.user_id
-        WHERE g.group_name = ? AND u.user_name = ?
-        ",
-        group_name, owner_user_name
-    )
-    .fetch_one(pool.get_ref())
-    .await;
-
-    let group_id = match group_id_result {
-        Ok(record) => record.group_id,
-        Err(_) => {
-            info!("Group not found: {}", group_name);
-            return HttpResponse::BadRequest().json(GetProjectDetailResponse {
+    // Check that the current user belongs to the group
+    let group_id = match require_group_member(pool.get_ref(), &current_user, owner_user_name, group_name).await {
+        Ok(membership) => membership.group_id,
+        Err(e) => {
+            info!("Group access denied for {} on {}: {}", current_user.user_name, group_name, e);
+            return HttpResponse::build(e.status_code()).json(GetProjectDetailResponse {
                 project_name: "".to_string(),
                 project_description: "".to_string(),
                 tags: Vec::new(),
@@ -109,7 +422,7 @@ pub async fn get_project_detail(
 // Handler to add a project
 pub async fn add_project(
     pool: web::Data<MySqlPool>,
-    req: HttpRequest,
+    current_user: CurrentUser,
     request: web::Json<AddProjectRequest>,
 ) -> impl Responder {
     let owner_user_name = &request.owner_user_name;
@@ -118,70 +431,28 @@ pub async fn add_project(
     let project_descr: &String = &request.project_descr;
     let tags = &request.tags;
 
-    // Get the current user name using session ID in the cookie
-    let session_id = match req.cookie("session_id") {
-        Some(cookie) => cookie.value().to_string(),
-        None => {
-            info!("Session ID not found in cookies for add_project");
-            return HttpResponse::BadRequest().json(AddProjectResponse {
-                success: false,
-                message: "Session ID not found".to_string(),
-            });
-        }
-    };
-
-    let session_result = sqlx::query!(
-        "SELECT u.user_name FROM Sessions_ s
-         JOIN Users_ u ON s.user_id = u.user_id
-         WHERE s.session_id = ? AND s.expires_at > NOW()",
-        session_id
-    )
-    .fetch_one(pool.get_ref())
-    .await;
-
-    let current_user_name = match session_result {
-        Ok(session) => session.user_name,
-        Err(_) => {
-            info!("Invalid or expired session ID: {}", session_id);
-            return HttpResponse::BadRequest().json(AddProjectResponse {
+    // Check that the current user belongs to the group
+    let membership = match require_group_member(pool.get_ref(), &current_user, owner_user_name, group_name).await {
+        Ok(membership) => membership,
+        Err(e) => {
+            info!("Group access denied for {} on {}: {}", current_user.user_name, group_name, e);
+            return HttpResponse::build(e.status_code()).json(AddProjectResponse {
                 success: false,
-                message: "Invalid or expired session ID".to_string(),
+                message: e.to_string(),
             });
         }
     };
+    let group_id = membership.group_id;
 
-    // Assert owner_user_name == current user name
-    if owner_user_name != &current_user_name {
-        return HttpResponse::BadRequest().json(AddProjectResponse {
+    // Check that the role of the member allows managing projects
+    if !membership.can(Permission::ManageProjects) {
+        info!("{} ({}) is not allowed to manage projects of {}", current_user.user_name, membership.role.as_str(), group_name);
+        return HttpResponse::Forbidden().json(AddProjectResponse {
             success: false,
-            message: "Unauthorized action".to_string(),
+            message: "Your role in this group does not allow this action".to_string(),
         });
     }
 
-    // Get group_id using group_name from Groups_
-    let group_id_result = sqlx::query!(
-        "
-        SELECT g.group_id 
-        FROM Groups_ g
-        JOIN Users_ u ON g.owner_user_id = u.user_id
-        WHERE g.group_name = ? AND u.user_name = ?
-        ",
-        group_name, owner_user_name
-    )
-    .fetch_one(pool.get_ref())
-    .await;
-
-    let group_id = match group_id_result {
-        Ok(record) => record.group_id,
-        Err(_) => {
-            info!("Group not found: {}", group_name);
-            return HttpResponse::BadRequest().json(AddProjectResponse {
-                success: false,
-                message: "Group not found".to_string(),
-            });
-        }
-    };
-
     // Add project to Projects_
     let insert_result = sqlx::query!(
         "INSERT INTO Projects_ (group_id, project_name, project_description) VALUES (?, ?, ?)",
@@ -254,7 +525,7 @@ pub async fn add_project(
 
 pub async fn update_project(
     pool: web::Data<MySqlPool>,
-    req: HttpRequest,
+    current_user: CurrentUser,
     request: web::Json<UpdateProjectRequest>,
 ) -> impl Responder {
     let owner_user_name = &request.owner_user_name;
@@ -264,70 +535,28 @@ pub async fn update_project(
     let new_project_descr = &request.new_project_descr;
     let new_tags = &request.new_tags;
 
-    // Get the current user name using session ID in the cookie
-    let session_id = match req.cookie("session_id") {
-        Some(cookie) => cookie.value().to_string(),
-        None => {
-            info!("Session ID not found in cookies for update_project");
-            return HttpResponse::BadRequest().json(UpdateProjectResponse {
-                success: false,
-                message: "Session ID not found".to_string(),
-            });
-        }
-    };
-
-    let session_result = sqlx::query!(
-        "SELECT u.user_name FROM Sessions_ s
-            JOIN Users_ u ON s.user_id = u.user_id
-            WHERE s.session_id = ? AND s.expires_at > NOW()",
-        session_id
-    )
-    .fetch_one(pool.get_ref())
-    .await;
-
-    let current_user_name = match session_result {
-        Ok(session) => session.user_name,
-        Err(_) => {
-            info!("Invalid or expired session ID: {}", session_id);
-            return HttpResponse::BadRequest().json(UpdateProjectResponse {
+    // Check that the current user belongs to the group
+    let membership = match require_group_member(pool.get_ref(), &current_user, owner_user_name, group_name).await {
+        Ok(membership) => membership,
+        Err(e) => {
+            info!("Group access denied for {} on {}: {}", current_user.user_name, group_name, e);
+            return HttpResponse::build(e.status_code()).json(UpdateProjectResponse {
                 success: false,
-                message: "Invalid or expired session ID".to_string(),
+                message: e.to_string(),
             });
         }
     };
+    let group_id = membership.group_id;
 
-    // Assert owner_user_name == current user name
-    if owner_user_name != &current_user_name {
-        return HttpResponse::BadRequest().json(UpdateProjectResponse {
+    // Check that the role of the member allows managing projects
+    if !membership.can(Permission::ManageProjects) {
+        info!("{} ({}) is not allowed to manage projects of {}", current_user.user_name, membership.role.as_str(), group_name);
+        return HttpResponse::Forbidden().json(UpdateProjectResponse {
             success: false,
-            message: "Unauthorized action".to_string(),
+            message: "Your role in this group does not allow this action".to_string(),
         });
     }
 
-    // Get group_id using group_name from Groups_
-    let group_id_result = sqlx::query!(
-        "
-        SELECT g.group_id 
-        FROM Groups_ g
-        JOIN Users_ u ON g.owner_user_id = u.user_id
-        WHERE g.group_name = ? AND u.user_name = ?
-        ",
-        group_name, owner_user_name
-    )
-    .fetch_one(pool.get_ref())
-    .await;
-
-    let group_id = match group_id_result {
-        Ok(record) => record.group_id,
-        Err(_) => {
-            info!("Group not found: {}", group_name);
-            return HttpResponse::BadRequest().json(UpdateProjectResponse {
-                success: false,
-                message: "Group not found".to_string(),
-            });
-        }
-    };
-
     // Get project_id and current details using project_name from Projects_
     let project_details_result = sqlx::query!(
         "
@@ -439,77 +668,35 @@ pub async fn update_project(
 
 pub async fn delete_project(
     pool: web::Data<MySqlPool>,
-    req: HttpRequest,
+    current_user: CurrentUser,
     request: web::Json<DeleteProjectRequest>,
 ) -> impl Responder {
     let owner_user_name = &request.owner_user_name;
     let group_name = &request.group_name;
     let project_name = &request.project_name;
 
-    // Get the current user name using session ID in the cookie
-    let session_id = match req.cookie("session_id") {
-        Some(cookie) => cookie.value().to_string(),
-        None => {
-            info!("Session ID not found in cookies for delete_project");
-            return HttpResponse::BadRequest().json(DeleteProjectResponse {
-                success: false,
-                message: "Session ID not found".to_string(),
-            });
-        }
-    };
-
-    let session_result = sqlx::query!(
-        "SELECT u.user_name FROM Sessions_ s
-         JOIN Users_ u ON s.user_id = u.user_id
-         WHERE s.session_id = ? AND s.expires_at > NOW()",
-        session_id
-    )
-    .fetch_one(pool.get_ref())
-    .await;
-
-    let current_user_name = match session_result {
-        Ok(session) => session.user_name,
-        Err(_) => {
-            info!("Invalid or expired session ID: {}", session_id);
-            return HttpResponse::BadRequest().json(DeleteProjectResponse {
+    // Check that the current user belongs to the group
+    let membership = match require_group_member(pool.get_ref(), &current_user, owner_user_name, group_name).await {
+        Ok(membership) => membership,
+        Err(e) => {
+            info!("Group access denied for {} on {}: {}", current_user.user_name, group_name, e);
+            return HttpResponse::build(e.status_code()).json(DeleteProjectResponse {
                 success: false,
-                message: "Invalid or expired session ID".to_string(),
+                message: e.to_string(),
             });
         }
     };
+    let group_id = membership.group_id;
 
-    // Assert owner_user_name == current user name
-    if owner_user_name != &current_user_name {
-        return HttpResponse::BadRequest().json(DeleteProjectResponse {
+    // Check that the role of the member allows managing projects
+    if !membership.can(Permission::ManageProjects) {
+        info!("{} ({}) is not allowed to manage projects of {}", current_user.user_name, membership.role.as_str(), group_name);
+        return HttpResponse::Forbidden().json(DeleteProjectResponse {
             success: false,
-            message: "Unauthorized action".to_string(),
+            message: "Your role in this group does not allow this action".to_string(),
         });
     }
 
-    // Get group_id using group_name from Groups_
-    let group_id_result = sqlx::query!(
-        "
-        SELECT g.group_id 
-        FROM Groups_ g
-        JOIN Users_ u ON g.owner_user_id = u.user_id
-        WHERE g.group_name = ? AND u.user_name = ?
-        ",
-        group_name, owner_user_name
-    )
-    .fetch_one(pool.get_ref())
-    .await;
-
-    let group_id = match group_id_result {
-        Ok(record) => record.group_id,
-        Err(_) => {
-            info!("Group not found: {}", group_name);
-            return HttpResponse::BadRequest().json(DeleteProjectResponse {
-                success: false,
-                message: "Group not found".to_string(),
-            });
-        }
-    };
-
     // Get project_id using project_name from Projects_
     let project_id_result = sqlx::query!(
         "
@@ -545,6 +732,46 @@ pub async fn delete_project(
         }
     };
 
+    // Note the attachment files of the project, whose rows go with it
+    let storage_names: Vec<String> = match sqlx::query_scalar!(
+        "SELECT storage_name FROM Attachments_ WHERE project_id = ?",
+        project_id
+    )
+    .fetch_all(&mut *tx)
+    .await
+    {
+        Ok(storage_names) => storage_names,
+        Err(e) => {
+            error!("Failed to fetch attachments of project {}: {}", project_id, e);
+            let _ = tx.rollback().await;
+            return HttpResponse::InternalServerError().json(DeleteProjectResponse {
+                success: false,
+                message: "Failed to fetch attachments".to_string(),
+            });
+        }
+    };
+
+    // Delete dependencies that touch tasks of the project
+    let delete_dependencies_result = sqlx::query!(
+        "
+        DELETE d FROM Dependencies_ d
+        JOIN Tasks_ t ON d.prev_task_id = t.task_id OR d.next_task_id = t.task_id
+        WHERE t.project_id = ?
+        ",
+        project_id
+    )
+    .execute(&mut *tx)
+    .await;
+
+    if let Err(e) = delete_dependencies_result {
+        error!("Failed to delete dependencies for project {}: {}", project_id, e);
+        let _ = tx.rollback().await;
+        return HttpResponse::InternalServerError().json(DeleteProjectResponse {
+            success: false,
+            message: "Failed to delete dependencies".to_string(),
+        });
+    }
+
     // Delete tasks associated with the project
     let delete_tasks_result = sqlx::query!(
         "
@@ -614,6 +841,8 @@ pub async fn delete_project(
         });
     }
 
+    remove_attachment_files(&storage_names).await;
+
     HttpResponse::Ok().json(DeleteProjectResponse {
         success: true,
         message: "Project deleted successfully".to_string(),
@@ -624,31 +853,20 @@ pub async fn delete_project(
 // Handler to get task details
 pub async fn get_task_detail(
     pool: web::Data<MySqlPool>,
-    _: HttpRequest,
+    current_user: CurrentUser,
     request: web::Json<GetTaskDetailRequest>,
 ) -> impl Responder {
     let owner_user_name = &request.owner_user_name;
     let group_name = &request.group_name;
     let project_name = &request.project_name;
+    let status_filter = &request.status_filter;
 
-    // Get group_id using group_name from Groups_
-    let group_id_result = sqlx::query!(
-        "
-        SELECT g.group_id 
-        FROM Groups_ g
-        JOIN Users_ u ON g.owner_user_id = u.user_id
-        WHERE g.group_name = ? AND u.user_name = ?
-        ",
-        group_name, owner_user_name
-    )
-    .fetch_one(pool.get_ref())
-    .await;
-
-    let group_id = match group_id_result {
-        Ok(record) => record.group_id,
-        Err(_) => {
-            info!("Group not found: {}", group_name);
-            return HttpResponse::BadRequest().json(GetTaskDetailResponse { tasks: Vec::new() });
+    // Check that the current user belongs to the group
+    let group_id = match require_group_member(pool.get_ref(), &current_user, owner_user_name, group_name).await {
+        Ok(membership) => membership.group_id,
+        Err(e) => {
+            info!("Group access denied for {} on {}: {}", current_user.user_name, group_name, e);
+            return HttpResponse::build(e.status_code()).json(GetTaskDetailResponse { tasks: Vec::new(), dependencies: Vec::new() });
         }
     };
 
@@ -668,16 +886,16 @@ pub async fn get_task_detail(
         Ok(record) => record.project_id,
         Err(_) => {
             info!("Project not found: {}", project_name);
-            return HttpResponse::BadRequest().json(GetTaskDetailResponse { tasks: Vec::new() });
+            return HttpResponse::BadRequest().json(GetTaskDetailResponse { tasks: Vec::new(), dependencies: Vec::new() });
         }
     };
 
     // Get tasks for the project
     let tasks_result = sqlx::query!(
         "
-        SELECT t.title AS task_title, u.user_name AS worker_name, t.start_time, t.end_time, t.description, p.project_name, GROUP_CONCAT(ta.tag_color SEPARATOR ',') AS tag_colors
+        SELECT t.task_id, t.parent_task_id, t.title AS task_title, COALESCE(u.user_name, '') AS `worker_name!`, (SELECT GROUP_CONCAT(au.user_name ORDER BY au.user_name SEPARATOR ',') FROM TaskAssignees_ tas JOIN Users_ au ON tas.user_id = au.user_id WHERE tas.task_id = t.task_id) AS worker_names, t.start_time, t.end_time, t.description, t.status, t.progress, p.project_name, GROUP_CONCAT(ta.tag_color SEPARATOR ',') AS tag_colors
         FROM Tasks_ t
-        JOIN Users_ u ON t.worker_user_id = u.user_id
+        LEFT JOIN Users_ u ON t.worker_user_id = u.user_id
         JOIN Projects_ p ON t.project_id = p.project_id
         LEFT JOIN TagProjectMapping_ tpm ON t.project_id = tpm.project_id
         LEFT JOIN Tags_ ta ON ta.tag_id = tpm.tag_id
@@ -689,124 +907,265 @@ pub async fn get_task_detail(
     .fetch_all(pool.get_ref())
     .await;
 
-    match tasks_result {    
+    let records = match tasks_result {
+        Ok(records) => records,
+        Err(e) => {
+            error!("Failed to fetch tasks for project_id {}: {}", project_id, e);
+            return HttpResponse::InternalServerError().json(GetTaskDetailResponse { tasks: Vec::new(), dependencies: Vec::new() });
+        }
+    };
+
+    // Roll the dates and progress of parent tasks up from their subtasks
+    let parents: Vec<(i32, Option<i32>)> = records.iter().map(|record| (record.task_id, record.parent_task_id)).collect();
+    let mut children = children_of(&parents);
+    let progress: HashMap<i32, TaskProgress> = records.iter().map(|record| (record.task_id, TaskProgress {
+        start_time: record.start_time,
+        end_time: record.end_time,
+        progress: record.progress,
+    })).collect();
+    let rolled = roll_up(&progress, &children);
+    for child_ids in children.values_mut() {
+        child_ids.sort_by_key(|task_id| rolled.get(task_id).map(|task| task.start_time));
+    }
+
+    let mut tasks: HashMap<i32, Task> = records.into_iter().map(|record| {
+        let task = rolled.get(&record.task_id).copied().unwrap_or(TaskProgress {
+            start_time: record.start_time,
+            end_time: record.end_time,
+            progress: record.progress,
+        });
+        (record.task_id, Task {
+            task_title: record.task_title,
+            worker_name: record.worker_name,
+            worker_names: record.worker_names.map(|names| names.split(',').map(|s| s.to_string()).collect()).unwrap_or_default(),
+            start_time: task.start_time.to_string(),
+            end_time: task.end_time.to_string(),
+            description: record.description,
+            project_name: record.project_name,
+            tag_colors: record.tag_colors.expect("TAG COLORS EMPTY, NEVER").split(',').map(|s| s.to_string()).collect(),
+            status: record.status,
+            progress: task.progress,
+        })
+    }).collect();
+
+    let tasks = build_task_tree(None, &children, &mut tasks, status_filter);
+
+    // Get dependency edges between the tasks of the project
+    let dependencies_result = sqlx::query!(
+        "
+        SELECT pt.title AS prev_task_title, nt.title AS next_task_title
+        FROM Dependencies_ d
+        JOIN Tasks_ pt ON d.prev_task_id = pt.task_id
+        JOIN Tasks_ nt ON d.next_task_id = nt.task_id
+        WHERE pt.project_id = ?
+        ",
+        project_id
+    )
+    .fetch_all(pool.get_ref())
+    .await;
+
+    match dependencies_result {
         Ok(records) => {
-            let tasks: Vec<Task> = records.into_iter().map(|record| Task {
-                task_title: record.task_title,
-                worker_name: record.worker_name,
-                start_time: record.start_time.to_string(),
-                end_time: record.end_time.to_string(),
-                description: record.description,
-                project_name: record.project_name,
-                tag_colors: record.tag_colors.expect("TAG COLORS EMPTY, NEVER").split(',').map(|s| s.to_string()).collect(),
+            let dependencies: Vec<Dependency> = records.into_iter().map(|record| Dependency {
+                prev_task_title: record.prev_task_title,
+                next_task_title: record.next_task_title,
             }).collect();
 
-            HttpResponse::Ok().json(GetTaskDetailResponse { tasks })
+            HttpResponse::Ok().json(GetTaskDetailResponse { tasks, dependencies })
         },
         Err(e) => {
-            error!("Failed to fetch tasks for project_id {}: {}", project_id, e);
-            HttpResponse::InternalServerError().json(GetTaskDetailResponse { tasks: Vec::new() })
+            error!("Failed to fetch dependencies for project_id {}: {}", project_id, e);
+            HttpResponse::InternalServerError().json(GetTaskDetailResponse { tasks: Vec::new(), dependencies: Vec::new() })
         }
     }
 }
 
 
-pub async fn add_task(
+// Handler to compute the critical path of a project
+pub async fn get_critical_path(
     pool: web::Data<MySqlPool>,
-    req: HttpRequest,
-    request: web::Json<AddTaskRequest>,
+    current_user: CurrentUser,
+    request: web::Json<GetCriticalPathRequest>,
 ) -> impl Responder {
     let owner_user_name = &request.owner_user_name;
     let group_name = &request.group_name;
     let project_name = &request.project_name;
-    let worker_name = &request.worker_name;
-    let task_title = &request.task_title;
-    let description = &request.description;
-
-    let format = format_description!(
-        "[year]-[month]-[day] [hour]:[minute]:[second]"
-    );
 
-    // Parse start_time and end_time
-    let start_time = 
-        PrimitiveDateTime::parse(&request.start_time, &format)
-        .expect("Failed to parse start time");
-    
-    let end_time = 
-        PrimitiveDateTime::parse(&request.end_time, &format)
-        .expect("Failed to parse end time");
-
-    // Check if start time is before end time
-    if start_time >= end_time {
-        return HttpResponse::BadRequest().json(AddTaskResponse {
-            success: false,
-            message: "Start time must be before end time".to_string(),
-        });
-    }
+    let empty_response = || GetCriticalPathResponse {
+        message: "".to_string(),
+        project_start: "".to_string(),
+        project_finish: "".to_string(),
+        critical_path: Vec::new(),
+        tasks: Vec::new(),
+    };
 
-    // Get the current user name using session ID in the cookie
-    let session_id = match req.cookie("session_id") {
-        Some(cookie) => cookie.value().to_string(),
-        None => {
-            info!("Session ID not found in cookies for add_task");
-            return HttpResponse::BadRequest().json(AddTaskResponse {
-                success: false,
-                message: "Session ID not found".to_string(),
-            });
+    // Check that the current user belongs to the group
+    let group_id = match require_group_member(pool.get_ref(), &current_user, owner_user_name, group_name).await {
+        Ok(membership) => membership.group_id,
+        Err(e) => {
+            info!("Group access denied for {} on {}: {}", current_user.user_name, group_name, e);
+            return HttpResponse::build(e.status_code()).json(empty_response());
         }
     };
 
-    let session_result = sqlx::query!(
-        "SELECT u.user_name FROM Sessions_ s
-         JOIN Users_ u ON s.user_id = u.user_id
-         WHERE s.session_id = ? AND s.expires_at > NOW()",
-        session_id
+    // Get project_id using project_name from Projects_
+    let project_id_result = sqlx::query!(
+        "
+        SELECT p.project_id 
+        FROM Projects_ p
+        WHERE p.group_id = ? AND p.project_name = ?
+        ",
+        group_id, project_name
     )
     .fetch_one(pool.get_ref())
     .await;
 
-    let current_user_name = match session_result {
-        Ok(session) => session.user_name,
+    let project_id = match project_id_result {
+        Ok(record) => record.project_id,
         Err(_) => {
-            info!("Invalid or expired session ID: {}", session_id);
-            return HttpResponse::BadRequest().json(AddTaskResponse {
-                success: false,
-                message: "Invalid or expired session ID".to_string(),
+            info!("Project not found: {}", project_name);
+            return HttpResponse::BadRequest().json(empty_response());
+        }
+    };
+
+    let schedule_result = match pool.acquire().await {
+        Ok(mut conn) => fetch_project_schedule(&mut conn, project_id).await,
+        Err(e) => Err(e),
+    };
+
+    let (tasks, edges) = match schedule_result {
+        Ok(schedule) => schedule,
+        Err(e) => {
+            error!("Failed to fetch schedule for project {}: {}", project_id, e);
+            return HttpResponse::InternalServerError().json(empty_response());
+        }
+    };
+
+    let critical_path = match compute_critical_path(&tasks, &edges) {
+        Ok(Some(critical_path)) => critical_path,
+        Ok(None) => return HttpResponse::Ok().json(empty_response()),
+        Err(cycle) => {
+            info!("Cannot compute critical path of project {}: {}", project_id, describe_cycle(&cycle));
+            return HttpResponse::BadRequest().json(GetCriticalPathResponse {
+                message: describe_cycle(&cycle),
+                ..empty_response()
             });
         }
     };
 
-    // Assert owner_user_name == current user name
-    if owner_user_name != &current_user_name {
+    let task_schedules: Vec<TaskSchedule> = critical_path.tasks.iter().map(|task| TaskSchedule {
+        task_title: tasks[&task.task_id].title.clone(),
+        earliest_start: task.earliest_start.to_string(),
+        earliest_finish: task.earliest_finish.to_string(),
+        latest_start: task.latest_start.to_string(),
+        latest_finish: task.latest_finish.to_string(),
+        total_float_minutes: task.total_float.whole_minutes(),
+        is_critical: task.total_float.is_zero(),
+    }).collect();
+
+    HttpResponse::Ok().json(GetCriticalPathResponse {
+        message: "".to_string(),
+        project_start: critical_path.project_start.to_string(),
+        project_finish: critical_path.project_finish.to_string(),
+        critical_path: critical_path.critical_task_ids.iter().map(|task_id| tasks[task_id].title.clone()).collect(),
+        tasks: task_schedules,
+    })
+}
+
+
+pub async fn add_task(
+    pool: web::Data<MySqlPool>,
+    current_user: CurrentUser,
+    request: web::Json<AddTaskRequest>,
+) -> impl Responder {
+    let owner_user_name = &request.owner_user_name;
+    let group_name = &request.group_name;
+    let project_name = &request.project_name;
+    let task_title = &request.task_title;
+    let description = &request.description;
+    let prev_task_titles = &request.prev_task_titles;
+    let next_task_titles = &request.next_task_titles;
+    let parent_task_title = &request.parent_task_title;
+    let progress = request.progress;
+
+    // Check the progress of the new task
+    if !(0..=100).contains(&progress) {
         return HttpResponse::BadRequest().json(AddTaskResponse {
             success: false,
-            message: "Unauthorized action".to_string(),
+            message: "Progress must be between 0 and 100".to_string(),
+            worker_conflicts: Vec::new(),
         });
     }
 
-    // Get group_id using group_name from Groups_
-    let group_id_result = sqlx::query!(
-        "
-        SELECT g.group_id 
-        FROM Groups_ g
-        JOIN Users_ u ON g.owner_user_id = u.user_id
-        WHERE g.group_name = ? AND u.user_name = ?
-        ",
-        group_name, owner_user_name
-    )
-    .fetch_one(pool.get_ref())
-    .await;
+    let format = format_description!(
+        "[year]-[month]-[day] [hour]:[minute]:[second]"
+    );
 
-    let group_id = match group_id_result {
-        Ok(record) => record.group_id,
-        Err(_) => {
-            info!("Group not found: {}", group_name);
-            return HttpResponse::BadRequest().json(AddTaskResponse {
+    // Parse start_time and end_time
+    let start_time = 
+        PrimitiveDateTime::parse(&request.start_time, &format)
+        .expect("Failed to parse start time");
+    
+    let end_time = 
+        PrimitiveDateTime::parse(&request.end_time, &format)
+        .expect("Failed to parse end time");
+
+    // Check if start time is before end time
+    if start_time >= end_time {
+        return HttpResponse::BadRequest().json(AddTaskResponse {
+            success: false,
+            message: "Start time must be before end time".to_string(),
+            worker_conflicts: Vec::new(),
+        });
+    }
+
+    // Check that the current user belongs to the group
+    let membership = match require_group_member(pool.get_ref(), &current_user, owner_user_name, group_name).await {
+        Ok(membership) => membership,
+        Err(e) => {
+            info!("Group access denied for {} on {}: {}", current_user.user_name, group_name, e);
+            return HttpResponse::build(e.status_code()).json(AddTaskResponse {
+                success: false,
+                message: e.to_string(),
+                worker_conflicts: Vec::new(),
+            });
+        }
+    };
+    let group_id = membership.group_id;
+
+    // Check that the role of the member allows managing tasks
+    if !membership.can(Permission::ManageTasks) {
+        info!("{} ({}) is not allowed to manage tasks of {}", current_user.user_name, membership.role.as_str(), group_name);
+        return HttpResponse::Forbidden().json(AddTaskResponse {
+            success: false,
+            message: "Your role in this group does not allow this action".to_string(),
+            worker_conflicts: Vec::new(),
+        });
+    }
+
+    // New tasks start in the first state of the workflow unless another state is given
+    let workflow = match fetch_workflow(pool.get_ref(), group_id).await {
+        Ok(workflow) => workflow,
+        Err(e) => {
+            error!("Failed to fetch workflow of group {}: {}", group_id, e);
+            return HttpResponse::InternalServerError().json(AddTaskResponse {
                 success: false,
-                message: "Group not found".to_string(),
+                message: "Failed to fetch workflow".to_string(),
+                worker_conflicts: Vec::new(),
             });
         }
     };
+    let status = if request.status.is_empty() {
+        workflow.initial_state().to_string()
+    } else if workflow.has_state(&request.status) {
+        request.status.clone()
+    } else {
+        return HttpResponse::BadRequest().json(AddTaskResponse {
+            success: false,
+            message: format!("Unknown task status: {}", request.status),
+            worker_conflicts: Vec::new(),
+        });
+    };
 
     // Get project_id using project_name from Projects_
     let project_id_result = sqlx::query!(
@@ -827,60 +1186,261 @@ pub async fn add_task(
             return HttpResponse::BadRequest().json(AddTaskResponse {
                 success: false,
                 message: "Project not found".to_string(),
+                worker_conflicts: Vec::new(),
             });
         }
     };
 
-    // Get worker_user_id using worker_name from Users_
-    let worker_id_result = sqlx::query!(
-        "
-        SELECT u.user_id 
-        FROM Users_ u
-        WHERE u.user_name = ?
-        ",
-        worker_name
-    )
-    .fetch_one(pool.get_ref())
-    .await;
+    // Get the ids of the assignees from Users_
+    let worker_names = if request.worker_names.is_empty() {
+        vec![request.worker_name.clone()]
+    } else {
+        request.worker_names.clone()
+    };
 
-    let worker_user_id = match worker_id_result {
-        Ok(record) => record.user_id,
-        Err(_) => {
-            info!("Worker not found: {}", worker_name);
+    let worker_user_ids = match find_worker_ids(pool.get_ref(), group_id, &worker_names).await {
+        Ok(worker_user_ids) => worker_user_ids,
+        Err(worker_name) => {
+            info!("Worker is not a member of group {}: {}", group_id, worker_name);
             return HttpResponse::BadRequest().json(AddTaskResponse {
                 success: false,
-                message: "Worker not found".to_string(),
+                message: format!("Worker is not a member of the group: {}", worker_name),
+                worker_conflicts: Vec::new(),
+            });
+        }
+    };
+
+    // Begin a transaction
+    let mut tx = match pool.begin().await {
+        Ok(transaction) => transaction,
+        Err(e) => {
+            error!("Failed to start transaction: {}", e);
+            return HttpResponse::InternalServerError().json(AddTaskResponse {
+                success: false,
+                message: "Failed to start transaction".to_string(),
+                worker_conflicts: Vec::new(),
+            });
+        }
+    };
+
+    // Validate against the schedule as it stands once other changes to the project are done
+    if let Err(e) = lock_project_schedule(&mut tx, project_id).await {
+        error!("Failed to lock project {}: {}", project_id, e);
+        let _ = tx.rollback().await;
+        return HttpResponse::InternalServerError().json(AddTaskResponse {
+            success: false,
+            message: "Failed to fetch project schedule".to_string(),
+            worker_conflicts: Vec::new(),
+        });
+    }
+
+    // Load the project schedule to validate the dependencies of the new task
+    let (mut tasks, mut edges) = match fetch_project_schedule(&mut tx, project_id).await {
+        Ok(schedule) => schedule,
+        Err(e) => {
+            error!("Failed to fetch schedule for project {}: {}", project_id, e);
+            let _ = tx.rollback().await;
+            return HttpResponse::InternalServerError().json(AddTaskResponse {
+                success: false,
+                message: "Failed to fetch project schedule".to_string(),
+                worker_conflicts: Vec::new(),
+            });
+        }
+    };
+
+    // The new task has no id yet, so 0 stands for it in the dependency graph.
+    // A title given twice adds a single dependency.
+    let new_task_id = 0;
+    let mut new_edges = Vec::new();
+    for prev_task_title in prev_task_titles {
+        match task_id_by_title(&tasks, prev_task_title) {
+            Some(prev_task_id) => {
+                if !new_edges.contains(&(prev_task_id, new_task_id)) {
+                    new_edges.push((prev_task_id, new_task_id));
+                }
+            }
+            None => {
+                info!("Task not found: {}", prev_task_title);
+                let _ = tx.rollback().await;
+                return HttpResponse::BadRequest().json(AddTaskResponse {
+                    success: false,
+                    message: format!("Task not found: {}", prev_task_title),
+                    worker_conflicts: Vec::new(),
+                });
+            }
+        }
+    }
+    for next_task_title in next_task_titles {
+        match task_id_by_title(&tasks, next_task_title) {
+            Some(next_task_id) => {
+                if !new_edges.contains(&(new_task_id, next_task_id)) {
+                    new_edges.push((new_task_id, next_task_id));
+                }
+            }
+            None => {
+                info!("Task not found: {}", next_task_title);
+                let _ = tx.rollback().await;
+                return HttpResponse::BadRequest().json(AddTaskResponse {
+                    success: false,
+                    message: format!("Task not found: {}", next_task_title),
+                    worker_conflicts: Vec::new(),
+                });
+            }
+        }
+    }
+
+    // Find the parent of the new task in the same project
+    let parent_task_id = if parent_task_title.is_empty() {
+        None
+    } else {
+        match task_id_by_title(&tasks, parent_task_title) {
+            Some(parent_task_id) => Some(parent_task_id),
+            None => {
+                info!("Parent task not found: {}", parent_task_title);
+                let _ = tx.rollback().await;
+                return HttpResponse::BadRequest().json(AddTaskResponse {
+                    success: false,
+                    message: format!("Parent task not found: {}", parent_task_title),
+                    worker_conflicts: Vec::new(),
+                });
+            }
+        }
+    };
+
+    tasks.insert(new_task_id, TaskWindow {
+        title: task_title.clone(),
+        start_time,
+        end_time,
+    });
+    edges.extend(new_edges.iter().copied());
+
+    if let Some(cycle) = find_cycle(&tasks, &edges) {
+        info!("Rejected task {} creating a cycle: {:?}", task_title, cycle);
+        let _ = tx.rollback().await;
+        return HttpResponse::BadRequest().json(AddTaskResponse {
+            success: false,
+            message: describe_cycle(&cycle),
+            worker_conflicts: Vec::new(),
+        });
+    }
+
+    let conflicts = find_schedule_conflicts(&tasks, &edges, Some(new_task_id));
+    if !conflicts.is_empty() {
+        info!("Rejected task {} with schedule conflicts: {:?}", task_title, conflicts);
+        let _ = tx.rollback().await;
+        return HttpResponse::BadRequest().json(AddTaskResponse {
+            success: false,
+            message: describe_conflicts(&conflicts),
+            worker_conflicts: Vec::new(),
+        });
+    }
+
+    // Check that the assignees are not booked on another task at the same time
+    let worker_conflicts = match fetch_worker_conflicts(pool.get_ref(), current_user.user_id, &worker_user_ids, start_time, end_time, &[]).await {
+        Ok(worker_conflicts) => worker_conflicts,
+        Err(e) => {
+            error!("Failed to check worker conflicts of users {:?}: {}", worker_user_ids, e);
+            let _ = tx.rollback().await;
+            return HttpResponse::InternalServerError().json(AddTaskResponse {
+                success: false,
+                message: "Failed to check worker conflicts".to_string(),
+                worker_conflicts: Vec::new(),
             });
         }
     };
 
+    if !worker_conflicts.is_empty() && membership.conflict_mode == ConflictMode::Strict {
+        info!("Rejected task {} double-booking workers {:?}", task_title, worker_names);
+        let _ = tx.rollback().await;
+        return HttpResponse::BadRequest().json(AddTaskResponse {
+            success: false,
+            message: describe_worker_conflicts(&worker_conflicts),
+            worker_conflicts,
+        });
+    }
+
     // Add task to Tasks_
     let insert_result = sqlx::query!(
         "
-        INSERT INTO Tasks_ (project_id, worker_user_id, title, description, start_time, end_time) 
-        VALUES (?, ?, ?, ?, ?, ?)",
-        project_id, worker_user_id, task_title, description, start_time, end_time
+        INSERT INTO Tasks_ (project_id, worker_user_id, title, description, start_time, end_time, status, progress, parent_task_id) 
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        project_id, worker_user_ids.first().copied(), task_title, description, start_time, end_time, status, progress, parent_task_id
     )
-    .execute(pool.get_ref())
+    .execute(&mut *tx)
     .await;
 
-    if let Err(e) = insert_result {
-        error!("Failed to add task to project {}: {}", project_id, e);
+    let task_id = match insert_result {
+        Ok(result) => result.last_insert_id() as i32,
+        Err(e) => {
+            error!("Failed to add task to project {}: {}", project_id, e);
+            let _ = tx.rollback().await;
+            return HttpResponse::InternalServerError().json(AddTaskResponse {
+                success: false,
+                message: "Failed to add task".to_string(),
+                worker_conflicts: Vec::new(),
+            });
+        }
+    };
+
+    // Add the assignees of the new task to TaskAssignees_
+    if let Err(e) = replace_assignees(&mut tx, task_id, &worker_user_ids).await {
+        error!("Failed to add assignees of task {}: {}", task_id, e);
+        let _ = tx.rollback().await;
+        return HttpResponse::InternalServerError().json(AddTaskResponse {
+            success: false,
+            message: "Failed to add task assignees".to_string(),
+            worker_conflicts: Vec::new(),
+        });
+    }
+
+    // Add the dependencies of the new task to Dependencies_
+    for (prev_task_id, next_task_id) in new_edges {
+        let prev_task_id = if prev_task_id == new_task_id { task_id } else { prev_task_id };
+        let next_task_id = if next_task_id == new_task_id { task_id } else { next_task_id };
+
+        let insert_dependency_result = sqlx::query!(
+            "INSERT INTO Dependencies_ (prev_task_id, next_task_id) VALUES (?, ?)",
+            prev_task_id, next_task_id
+        )
+        .execute(&mut *tx)
+        .await;
+
+        if let Err(e) = insert_dependency_result {
+            error!("Failed to add dependency {} -> {}: {}", prev_task_id, next_task_id, e);
+            let _ = tx.rollback().await;
+            return HttpResponse::InternalServerError().json(AddTaskResponse {
+                success: false,
+                message: "Failed to add task dependencies".to_string(),
+                worker_conflicts: Vec::new(),
+            });
+        }
+    }
+
+    // Commit the transaction
+    if let Err(e) = tx.commit().await {
+        error!("Failed to commit transaction: {}", e);
         return HttpResponse::InternalServerError().json(AddTaskResponse {
             success: false,
-            message: "Failed to add task".to_string(),
+            message: "Failed to commit transaction".to_string(),
+            worker_conflicts: Vec::new(),
         });
     }
 
+    // Let the assignees know about their new task
+    let recipients: Vec<i32> = worker_user_ids.iter().copied().filter(|user_id| *user_id != current_user.user_id).collect();
+    let message = format!("{} assigned you to task '{}' in {}", current_user.user_name, task_title, project_name);
+    notify_users(pool.get_ref(), &recipients, NotificationKind::TaskAssigned, &message, Some(group_id), Some(task_id)).await;
+
     HttpResponse::Ok().json(AddTaskResponse {
         success: true,
         message: "Task added successfully".to_string(),
+        worker_conflicts,
     })
 }
 
 pub async fn update_task(
     pool: web::Data<MySqlPool>,
-    req: HttpRequest,
+    current_user: CurrentUser,
     request: web::Json<UpdateTaskRequest>,
 ) -> impl Responder {
     let owner_user_name = &request.owner_user_name;
@@ -892,71 +1452,38 @@ pub async fn update_task(
     let new_description = &request.new_description;
     let new_start_time = &request.new_start_time;
     let new_end_time = &request.new_end_time;
-
-    // Get the current user name using session ID in the cookie
-    let session_id = match req.cookie("session_id") {
-        Some(cookie) => cookie.value().to_string(),
-        None => {
-            info!("Session ID not found in cookies for update_task");
-            return HttpResponse::BadRequest().json(UpdateTaskResponse {
-                success: false,
-                message: "Session ID not found".to_string(),
-            });
-        }
-    };
-
-    let session_result = sqlx::query!(
-        "SELECT u.user_name FROM Sessions_ s
-         JOIN Users_ u ON s.user_id = u.user_id
-         WHERE s.session_id = ? AND s.expires_at > NOW()",
-        session_id
-    )
-    .fetch_one(pool.get_ref())
-    .await;
-
-    let current_user_name = match session_result {
-        Ok(session) => session.user_name,
-        Err(_) => {
-            info!("Invalid or expired session ID: {}", session_id);
-            return HttpResponse::BadRequest().json(UpdateTaskResponse {
+    let new_prev_task_titles = &request.new_prev_task_titles;
+    let new_next_task_titles = &request.new_next_task_titles;
+    let cascade = request.cascade;
+    let new_status = &request.new_status;
+    let new_progress = request.new_progress;
+
+    // Check that the current user belongs to the group
+    let membership = match require_group_member(pool.get_ref(), &current_user, owner_user_name, group_name).await {
+        Ok(membership) => membership,
+        Err(e) => {
+            info!("Group access denied for {} on {}: {}", current_user.user_name, group_name, e);
+            return HttpResponse::build(e.status_code()).json(UpdateTaskResponse {
                 success: false,
-                message: "Invalid or expired session ID".to_string(),
+                message: e.to_string(),
+                moved_tasks: Vec::new(),
+                worker_conflicts: Vec::new(),
             });
         }
     };
+    let group_id = membership.group_id;
 
-    // Assert owner_user_name == current user name
-    if owner_user_name != &current_user_name {
-        return HttpResponse::BadRequest().json(UpdateTaskResponse {
+    // Check that the role of the member allows managing tasks
+    if !membership.can(Permission::ManageTasks) {
+        info!("{} ({}) is not allowed to manage tasks of {}", current_user.user_name, membership.role.as_str(), group_name);
+        return HttpResponse::Forbidden().json(UpdateTaskResponse {
             success: false,
-            message: "Unauthorized action".to_string(),
+            message: "Your role in this group does not allow this action".to_string(),
+            moved_tasks: Vec::new(),
+            worker_conflicts: Vec::new(),
         });
     }
 
-    // Get group_id using group_name from Groups_
-    let group_id_result = sqlx::query!(
-        "
-        SELECT g.group_id 
-        FROM Groups_ g
-        JOIN Users_ u ON g.owner_user_id = u.user_id
-        WHERE g.group_name = ? AND u.user_name = ?
-        ",
-        group_name, owner_user_name
-    )
-    .fetch_one(pool.get_ref())
-    .await;
-
-    let group_id = match group_id_result {
-        Ok(record) => record.group_id,
-        Err(_) => {
-            info!("Group not found: {}", group_name);
-            return HttpResponse::BadRequest().json(UpdateTaskResponse {
-                success: false,
-                message: "Group not found".to_string(),
-            });
-        }
-    };
-
     // Get project_id using project_name from Projects_
     let project_id_result = sqlx::query!(
         "
@@ -976,36 +1503,71 @@ pub async fn update_task(
             return HttpResponse::BadRequest().json(UpdateTaskResponse {
                 success: false,
                 message: "Project not found".to_string(),
+                moved_tasks: Vec::new(),
+                worker_conflicts: Vec::new(),
+            });
+        }
+    };
+
+    // Begin a transaction
+    let mut tx = match pool.begin().await {
+        Ok(transaction) => transaction,
+        Err(e) => {
+            error!("Failed to start transaction: {}", e);
+            return HttpResponse::InternalServerError().json(UpdateTaskResponse {
+                success: false,
+                message: "Failed to start transaction".to_string(),
+                moved_tasks: Vec::new(),
+                worker_conflicts: Vec::new(),
             });
         }
     };
 
+    // Read the task and validate the change once other changes to the project are done,
+    // so concurrent updates do not overwrite each other
+    if let Err(e) = lock_project_schedule(&mut tx, project_id).await {
+        error!("Failed to lock project {}: {}", project_id, e);
+        let _ = tx.rollback().await;
+        return HttpResponse::InternalServerError().json(UpdateTaskResponse {
+            success: false,
+            message: "Failed to fetch project schedule".to_string(),
+            moved_tasks: Vec::new(),
+            worker_conflicts: Vec::new(),
+        });
+    }
+
     // Get current task details
     let task_details_result = sqlx::query!(
         "
-        SELECT t.task_id, t.title, t.worker_user_id, t.description, t.start_time, t.end_time
+        SELECT t.task_id, t.title, t.worker_user_id, t.description, t.start_time, t.end_time, t.status, t.progress, t.parent_task_id
         FROM Tasks_ t
         WHERE t.project_id = ? AND t.title = ?
         ",
         project_id, task_title
     )
-    .fetch_one(pool.get_ref())
+    .fetch_one(&mut *tx)
     .await;
 
-    let (task_id, current_task_title, current_worker_user_id, current_description, current_start_time, current_end_time) = match task_details_result {
+    let (task_id, current_task_title, current_worker_user_id, current_description, current_start_time, current_end_time, current_status, current_progress, current_parent_task_id) = match task_details_result {
         Ok(record) => (
             record.task_id,
             record.title,
-            record.worker_user_id.expect("worker id should exist"),
+            record.worker_user_id,
             record.description,
             record.start_time,
             record.end_time,
+            record.status,
+            record.progress,
+            record.parent_task_id,
         ),
         Err(_) => {
             info!("Task not found: {}", task_title);
+            let _ = tx.rollback().await;
             return HttpResponse::BadRequest().json(UpdateTaskResponse {
                 success: false,
                 message: "Task not found".to_string(),
+                moved_tasks: Vec::new(),
+                worker_conflicts: Vec::new(),
             });
         }
     };
@@ -1017,33 +1579,57 @@ pub async fn update_task(
         new_task_title
     };
 
-    let final_worker_user_id = if new_worker_name.is_empty() {
-        current_worker_user_id
-    } else {
-        // Get worker_user_id using new_worker_name from Users_
-        let worker_id_result = sqlx::query!(
-            "
-            SELECT u.user_id 
-            FROM Users_ u
-            WHERE u.user_name = ?
-            ",
-            new_worker_name
-        )
-        .fetch_one(pool.get_ref())
-        .await;
+    // New assignees replace the current ones; the single new_worker_name is kept for older clients
+    let new_worker_names = match &request.new_worker_names {
+        Some(worker_names) => Some(worker_names.clone()),
+        None if !new_worker_name.is_empty() => Some(vec![new_worker_name.clone()]),
+        None => None,
+    };
 
-        match worker_id_result {
-            Ok(record) => record.user_id,
-            Err(_) => {
-                info!("Worker not found: {}", new_worker_name);
+    let new_worker_user_ids = match new_worker_names {
+        Some(worker_names) => match find_worker_ids(pool.get_ref(), group_id, &worker_names).await {
+            Ok(worker_user_ids) => Some(worker_user_ids),
+            Err(worker_name) => {
+                info!("Worker is not a member of group {}: {}", group_id, worker_name);
+                let _ = tx.rollback().await;
                 return HttpResponse::BadRequest().json(UpdateTaskResponse {
                     success: false,
-                    message: "Worker not found".to_string(),
+                    message: format!("Worker is not a member of the group: {}", worker_name),
+                    moved_tasks: Vec::new(),
+                    worker_conflicts: Vec::new(),
                 });
             }
+        },
+        None => None,
+    };
+
+    let final_worker_user_id = match &new_worker_user_ids {
+        Some(worker_user_ids) => worker_user_ids.first().copied(),
+        None => current_worker_user_id,
+    };
+
+    let current_worker_user_ids = match sqlx::query_scalar!("SELECT user_id FROM TaskAssignees_ WHERE task_id = ?", task_id)
+        .fetch_all(&mut *tx)
+        .await
+    {
+        Ok(worker_user_ids) => worker_user_ids,
+        Err(e) => {
+            error!("Failed to fetch assignees of task {}: {}", task_id, e);
+            let _ = tx.rollback().await;
+            return HttpResponse::InternalServerError().json(UpdateTaskResponse {
+                success: false,
+                message: "Failed to fetch task assignees".to_string(),
+                moved_tasks: Vec::new(),
+                worker_conflicts: Vec::new(),
+            });
         }
     };
 
+    let final_worker_user_ids = match &new_worker_user_ids {
+        Some(worker_user_ids) => worker_user_ids.clone(),
+        None => current_worker_user_ids.clone(),
+    };
+
     let final_description = if new_description.is_empty() {
         &current_description
     } else {
@@ -1056,9 +1642,12 @@ pub async fn update_task(
         match PrimitiveDateTime::parse(new_start_time, format_description!("[year]-[month]-[day] [hour]:[minute]:[second]")) {
             Ok(time) => time,
             Err(_) => {
+                let _ = tx.rollback().await;
                 return HttpResponse::BadRequest().json(UpdateTaskResponse {
                     success: false,
                     message: "Invalid start time format".to_string(),
+                    moved_tasks: Vec::new(),
+                    worker_conflicts: Vec::new(),
                 });
             }
         }
@@ -1070,9 +1659,12 @@ pub async fn update_task(
         match PrimitiveDateTime::parse(new_end_time, format_description!("[year]-[month]-[day] [hour]:[minute]:[second]")) {
             Ok(time) => time,
             Err(_) => {
+                let _ = tx.rollback().await;
                 return HttpResponse::BadRequest().json(UpdateTaskResponse {
                     success: false,
                     message: "Invalid end time format".to_string(),
+                    moved_tasks: Vec::new(),
+                    worker_conflicts: Vec::new(),
                 });
             }
         }
@@ -1080,41 +1672,462 @@ pub async fn update_task(
 
     // Check if the new start time is before the new end time
     if final_start_time >= final_end_time {
+        let _ = tx.rollback().await;
         return HttpResponse::BadRequest().json(UpdateTaskResponse {
             success: false,
             message: "Start time must be before end time".to_string(),
+            moved_tasks: Vec::new(),
+            worker_conflicts: Vec::new(),
         });
     }
-    
-    // Update task details in Tasks_
-    let update_result = sqlx::query!(
-        "
-        UPDATE Tasks_
-        SET title = ?, worker_user_id = ?, description = ?, start_time = ?, end_time = ?
-        WHERE task_id = ?
-        ",
-        final_task_title, final_worker_user_id, final_description, final_start_time, final_end_time, task_id
-    )
-    .execute(pool.get_ref())
-    .await;
 
-    if let Err(e) = update_result {
-        error!("Failed to update task {}: {}", task_id, e);
+    // The status may only move along a transition of the group workflow
+    let final_status: String = if new_status.is_empty() {
+        current_status
+    } else {
+        let workflow = match fetch_workflow(pool.get_ref(), group_id).await {
+            Ok(workflow) => workflow,
+            Err(e) => {
+                error!("Failed to fetch workflow of group {}: {}", group_id, e);
+                let _ = tx.rollback().await;
+                return HttpResponse::InternalServerError().json(UpdateTaskResponse {
+                    success: false,
+                    message: "Failed to fetch workflow".to_string(),
+                    moved_tasks: Vec::new(),
+                    worker_conflicts: Vec::new(),
+                });
+            }
+        };
+        if !workflow.has_state(new_status) {
+            let _ = tx.rollback().await;
+            return HttpResponse::BadRequest().json(UpdateTaskResponse {
+                success: false,
+                message: format!("Unknown task status: {}", new_status),
+                moved_tasks: Vec::new(),
+                worker_conflicts: Vec::new(),
+            });
+        }
+        if !workflow.allows(&current_status, new_status) {
+            let _ = tx.rollback().await;
+            return HttpResponse::BadRequest().json(UpdateTaskResponse {
+                success: false,
+                message: format!("Cannot move task from {} to {}", current_status, new_status),
+                moved_tasks: Vec::new(),
+                worker_conflicts: Vec::new(),
+            });
+        }
+        new_status.clone()
+    };
+
+    let final_progress = new_progress.unwrap_or(current_progress);
+    if !(0..=100).contains(&final_progress) {
+        let _ = tx.rollback().await;
+        return HttpResponse::BadRequest().json(UpdateTaskResponse {
+            success: false,
+            message: "Progress must be between 0 and 100".to_string(),
+            moved_tasks: Vec::new(),
+            worker_conflicts: Vec::new(),
+        });
+    }
+    
+    // Load the project schedule to validate the task against its dependencies
+    let (mut tasks, mut edges) = match fetch_project_schedule(&mut tx, project_id).await {
+        Ok(schedule) => schedule,
+        Err(e) => {
+            error!("Failed to fetch schedule for project {}: {}", project_id, e);
+            let _ = tx.rollback().await;
+            return HttpResponse::InternalServerError().json(UpdateTaskResponse {
+                success: false,
+                message: "Failed to fetch project schedule".to_string(),
+                moved_tasks: Vec::new(),
+                worker_conflicts: Vec::new(),
+            });
+        }
+    };
+
+    // Move the task under a new parent when one is given
+    let final_parent_task_id = match &request.new_parent_task_title {
+        None => current_parent_task_id,
+        Some(parent_task_title) if parent_task_title.is_empty() => None,
+        Some(parent_task_title) => {
+            let parent_task_id = match task_id_by_title(&tasks, parent_task_title) {
+                Some(parent_task_id) => parent_task_id,
+                None => {
+                    info!("Parent task not found: {}", parent_task_title);
+                    let _ = tx.rollback().await;
+                    return HttpResponse::BadRequest().json(UpdateTaskResponse {
+                        success: false,
+                        message: format!("Parent task not found: {}", parent_task_title),
+                        moved_tasks: Vec::new(),
+                        worker_conflicts: Vec::new(),
+                    });
+                }
+            };
+
+            // A task cannot be moved under itself or one of its subtasks
+            let parents = match fetch_task_parents(&mut *tx, project_id).await {
+                Ok(parents) => parents,
+                Err(e) => {
+                    error!("Failed to fetch task hierarchy for project {}: {}", project_id, e);
+                    let _ = tx.rollback().await;
+                    return HttpResponse::InternalServerError().json(UpdateTaskResponse {
+                        success: false,
+                        message: "Failed to fetch task hierarchy".to_string(),
+                        moved_tasks: Vec::new(),
+                        worker_conflicts: Vec::new(),
+                    });
+                }
+            };
+            if subtree(task_id, &children_of(&parents)).contains(&parent_task_id) {
+                let _ = tx.rollback().await;
+                return HttpResponse::BadRequest().json(UpdateTaskResponse {
+                    success: false,
+                    message: "A task cannot be moved under itself or one of its subtasks".to_string(),
+                    moved_tasks: Vec::new(),
+                    worker_conflicts: Vec::new(),
+                });
+            }
+
+            Some(parent_task_id)
+        }
+    };
+
+    // Replace the dependencies of the task when new ones are given, ignoring repeated titles
+    let mut new_prev_task_ids = Vec::new();
+    for prev_task_title in new_prev_task_titles {
+        match task_id_by_title(&tasks, prev_task_title) {
+            Some(prev_task_id) => {
+                if !new_prev_task_ids.contains(&prev_task_id) {
+                    new_prev_task_ids.push(prev_task_id);
+                }
+            }
+            None => {
+                info!("Task not found: {}", prev_task_title);
+                let _ = tx.rollback().await;
+                return HttpResponse::BadRequest().json(UpdateTaskResponse {
+                    success: false,
+                    message: format!("Task not found: {}", prev_task_title),
+                    moved_tasks: Vec::new(),
+                    worker_conflicts: Vec::new(),
+                });
+            }
+        }
+    }
+
+    let mut new_next_task_ids = Vec::new();
+    for next_task_title in new_next_task_titles {
+        match task_id_by_title(&tasks, next_task_title) {
+            Some(next_task_id) => {
+                if !new_next_task_ids.contains(&next_task_id) {
+                    new_next_task_ids.push(next_task_id);
+                }
+            }
+            None => {
+                info!("Task not found: {}", next_task_title);
+                let _ = tx.rollback().await;
+                return HttpResponse::BadRequest().json(UpdateTaskResponse {
+                    success: false,
+                    message: format!("Task not found: {}", next_task_title),
+                    moved_tasks: Vec::new(),
+                    worker_conflicts: Vec::new(),
+                });
+            }
+        }
+    }
+
+    if !new_prev_task_ids.is_empty() {
+        edges.retain(|(_, next_task_id)| *next_task_id != task_id);
+        edges.extend(new_prev_task_ids.iter().map(|prev_task_id| (*prev_task_id, task_id)));
+    }
+
+    if !new_next_task_ids.is_empty() {
+        edges.retain(|(prev_task_id, _)| *prev_task_id != task_id);
+        edges.extend(new_next_task_ids.iter().map(|next_task_id| (task_id, *next_task_id)));
+    }
+
+    tasks.insert(task_id, TaskWindow {
+        title: final_task_title.clone(),
+        start_time: final_start_time,
+        end_time: final_end_time,
+    });
+
+    if let Some(cycle) = find_cycle(&tasks, &edges) {
+        info!("Rejected update of task {} creating a cycle: {:?}", task_id, cycle);
+        let _ = tx.rollback().await;
+        return HttpResponse::BadRequest().json(UpdateTaskResponse {
+            success: false,
+            message: describe_cycle(&cycle),
+            moved_tasks: Vec::new(),
+            worker_conflicts: Vec::new(),
+        });
+    }
+
+    // Push dependent tasks forward so that successors no longer conflict with the task
+    let moved_tasks = if cascade {
+        match cascade_reschedule(&mut tasks, &edges, task_id) {
+            Ok(moved_tasks) => moved_tasks,
+            Err(moved_task_title) => {
+                info!("Rejected update of task {} pushing {} out of range", task_id, moved_task_title);
+                let _ = tx.rollback().await;
+                return HttpResponse::BadRequest().json(UpdateTaskResponse {
+                    success: false,
+                    message: format!("Dependent task '{}' cannot be moved that far", moved_task_title),
+                    moved_tasks: Vec::new(),
+                    worker_conflicts: Vec::new(),
+                });
+            }
+        }
+    } else {
+        Vec::new()
+    };
+
+    let conflicts = find_schedule_conflicts(&tasks, &edges, Some(task_id));
+    if !conflicts.is_empty() {
+        info!("Rejected update of task {} with schedule conflicts: {:?}", task_id, conflicts);
+        let _ = tx.rollback().await;
+        return HttpResponse::BadRequest().json(UpdateTaskResponse {
+            success: false,
+            message: describe_conflicts(&conflicts),
+            moved_tasks: Vec::new(),
+            worker_conflicts: Vec::new(),
+        });
+    }
+
+    // Check that the assignees of the task and of the tasks moved with it are not booked
+    // on another task at the same time
+    let final_window = TaskWindow {
+        title: final_task_title.clone(),
+        start_time: final_start_time,
+        end_time: final_end_time,
+    };
+    let mut rewritten_task_ids = vec![task_id];
+    rewritten_task_ids.extend(moved_tasks.iter().map(|(moved_task_id, _)| *moved_task_id));
+
+    let worker_conflicts_result = match fetch_worker_conflicts(pool.get_ref(), current_user.user_id, &final_worker_user_ids, final_start_time, final_end_time, &rewritten_task_ids).await {
+        Ok(mut worker_conflicts) => fetch_cascade_worker_conflicts(
+            pool.get_ref(),
+            current_user.user_id,
+            (task_id, &final_window, &final_worker_user_ids),
+            &moved_tasks,
+            group_name,
+            project_name,
+        )
+        .await
+        .map(|cascade_conflicts| {
+            worker_conflicts.extend(cascade_conflicts);
+            worker_conflicts
+        }),
+        Err(e) => Err(e),
+    };
+
+    let worker_conflicts = match worker_conflicts_result {
+        Ok(worker_conflicts) => worker_conflicts,
+        Err(e) => {
+            error!("Failed to check worker conflicts of users {:?}: {}", final_worker_user_ids, e);
+            let _ = tx.rollback().await;
+            return HttpResponse::InternalServerError().json(UpdateTaskResponse {
+                success: false,
+                message: "Failed to check worker conflicts".to_string(),
+                moved_tasks: Vec::new(),
+                worker_conflicts: Vec::new(),
+            });
+        }
+    };
+
+    if !worker_conflicts.is_empty() && membership.conflict_mode == ConflictMode::Strict {
+        info!("Rejected update of task {} double-booking its workers", task_id);
+        let _ = tx.rollback().await;
+        return HttpResponse::BadRequest().json(UpdateTaskResponse {
+            success: false,
+            message: describe_worker_conflicts(&worker_conflicts),
+            moved_tasks: Vec::new(),
+            worker_conflicts,
+        });
+    }
+
+    // Update task details in Tasks_
+    let update_result = sqlx::query!(
+        "
+        UPDATE Tasks_
+        SET title = ?, worker_user_id = ?, description = ?, start_time = ?, end_time = ?, status = ?, progress = ?, parent_task_id = ?
+        WHERE task_id = ?
+        ",
+        final_task_title, final_worker_user_id, final_description, final_start_time, final_end_time, final_status, final_progress, final_parent_task_id, task_id
+    )
+    .execute(&mut *tx)
+    .await;
+
+    if let Err(e) = update_result {
+        error!("Failed to update task {}: {}", task_id, e);
+        let _ = tx.rollback().await;
         return HttpResponse::InternalServerError().json(UpdateTaskResponse {
             success: false,
             message: "Failed to update task".to_string(),
+            moved_tasks: Vec::new(),
+            worker_conflicts: Vec::new(),
+        });
+    }
+
+    // Replace the assignees of the task in TaskAssignees_
+    if let Some(worker_user_ids) = &new_worker_user_ids {
+        if let Err(e) = replace_assignees(&mut tx, task_id, worker_user_ids).await {
+            error!("Failed to update assignees of task {}: {}", task_id, e);
+            let _ = tx.rollback().await;
+            return HttpResponse::InternalServerError().json(UpdateTaskResponse {
+                success: false,
+                message: "Failed to update task assignees".to_string(),
+                moved_tasks: Vec::new(),
+                worker_conflicts: Vec::new(),
+            });
+        }
+    }
+
+    // Replace the predecessors of the task in Dependencies_
+    if !new_prev_task_ids.is_empty() {
+        let clear_result = sqlx::query!(
+            "DELETE FROM Dependencies_ WHERE next_task_id = ?",
+            task_id
+        )
+        .execute(&mut *tx)
+        .await;
+
+        if let Err(e) = clear_result {
+            error!("Failed to clear predecessors of task {}: {}", task_id, e);
+            let _ = tx.rollback().await;
+            return HttpResponse::InternalServerError().json(UpdateTaskResponse {
+                success: false,
+                message: "Failed to update task dependencies".to_string(),
+                moved_tasks: Vec::new(),
+                worker_conflicts: Vec::new(),
+            });
+        }
+
+        for prev_task_id in &new_prev_task_ids {
+            let insert_result = sqlx::query!(
+                "INSERT INTO Dependencies_ (prev_task_id, next_task_id) VALUES (?, ?)",
+                prev_task_id, task_id
+            )
+            .execute(&mut *tx)
+            .await;
+
+            if let Err(e) = insert_result {
+                error!("Failed to add dependency {} -> {}: {}", prev_task_id, task_id, e);
+                let _ = tx.rollback().await;
+                return HttpResponse::InternalServerError().json(UpdateTaskResponse {
+                    success: false,
+                    message: "Failed to update task dependencies".to_string(),
+                    moved_tasks: Vec::new(),
+                    worker_conflicts: Vec::new(),
+                });
+            }
+        }
+    }
+
+    // Replace the successors of the task in Dependencies_
+    if !new_next_task_ids.is_empty() {
+        let clear_result = sqlx::query!(
+            "DELETE FROM Dependencies_ WHERE prev_task_id = ?",
+            task_id
+        )
+        .execute(&mut *tx)
+        .await;
+
+        if let Err(e) = clear_result {
+            error!("Failed to clear successors of task {}: {}", task_id, e);
+            let _ = tx.rollback().await;
+            return HttpResponse::InternalServerError().json(UpdateTaskResponse {
+                success: false,
+                message: "Failed to update task dependencies".to_string(),
+                moved_tasks: Vec::new(),
+                worker_conflicts: Vec::new(),
+            });
+        }
+
+        for next_task_id in &new_next_task_ids {
+            let insert_result = sqlx::query!(
+                "INSERT INTO Dependencies_ (prev_task_id, next_task_id) VALUES (?, ?)",
+                task_id, next_task_id
+            )
+            .execute(&mut *tx)
+            .await;
+
+            if let Err(e) = insert_result {
+                error!("Failed to add dependency {} -> {}: {}", task_id, next_task_id, e);
+                let _ = tx.rollback().await;
+                return HttpResponse::InternalServerError().json(UpdateTaskResponse {
+                    success: false,
+                    message: "Failed to update task dependencies".to_string(),
+                    moved_tasks: Vec::new(),
+                    worker_conflicts: Vec::new(),
+                });
+            }
+        }
+    }
+
+    // Move the dependent tasks pushed by the cascade
+    for (moved_task_id, moved_task) in &moved_tasks {
+        let move_result = sqlx::query!(
+            "UPDATE Tasks_ SET start_time = ?, end_time = ? WHERE task_id = ?",
+            moved_task.start_time, moved_task.end_time, moved_task_id
+        )
+        .execute(&mut *tx)
+        .await;
+
+        if let Err(e) = move_result {
+            error!("Failed to reschedule dependent task {}: {}", moved_task_id, e);
+            let _ = tx.rollback().await;
+            return HttpResponse::InternalServerError().json(UpdateTaskResponse {
+                success: false,
+                message: "Failed to reschedule dependent tasks".to_string(),
+                moved_tasks: Vec::new(),
+                worker_conflicts: Vec::new(),
+            });
+        }
+    }
+
+    // Commit the transaction
+    if let Err(e) = tx.commit().await {
+        error!("Failed to commit transaction: {}", e);
+        return HttpResponse::InternalServerError().json(UpdateTaskResponse {
+            success: false,
+            message: "Failed to commit transaction".to_string(),
+            moved_tasks: Vec::new(),
+            worker_conflicts: Vec::new(),
         });
     }
 
+    info!("Task {} updated, {} dependent tasks moved", task_id, moved_tasks.len());
+
+    // Let the workers know about the change: new assignees of the task, then the assignees of rescheduled tasks
+    let newly_assigned: Vec<i32> = final_worker_user_ids
+        .iter()
+        .copied()
+        .filter(|user_id| !current_worker_user_ids.contains(user_id) && *user_id != current_user.user_id)
+        .collect();
+    let message = format!("{} assigned you to task '{}' in {}", current_user.user_name, final_task_title, project_name);
+    notify_users(pool.get_ref(), &newly_assigned, NotificationKind::TaskAssigned, &message, Some(group_id), Some(task_id)).await;
+
+    if final_start_time != current_start_time || final_end_time != current_end_time {
+        notify_rescheduled(pool.get_ref(), &current_user, group_id, task_id, &final_window, &newly_assigned).await;
+    }
+    for (moved_task_id, moved_task) in &moved_tasks {
+        notify_rescheduled(pool.get_ref(), &current_user, group_id, *moved_task_id, moved_task, &[]).await;
+    }
     HttpResponse::Ok().json(UpdateTaskResponse {
         success: true,
         message: "Task updated successfully".to_string(),
+        moved_tasks: moved_tasks.into_iter().map(|(_, moved_task)| MovedTask {
+            task_title: moved_task.title,
+            start_time: moved_task.start_time.to_string(),
+            end_time: moved_task.end_time.to_string(),
+        }).collect(),
+        worker_conflicts,
     })
 }
 
 pub async fn delete_task(
     pool: web::Data<MySqlPool>,
-    req: HttpRequest,
+    current_user: CurrentUser,
     request: web::Json<DeleteTaskRequest>,
 ) -> impl Responder {
     let owner_user_name = &request.owner_user_name;
@@ -1122,70 +2135,28 @@ pub async fn delete_task(
     let project_name = &request.project_name;
     let task_title = &request.task_title;
 
-    // Get the current user name using session ID in the cookie
-    let session_id = match req.cookie("session_id") {
-        Some(cookie) => cookie.value().to_string(),
-        None => {
-            info!("Session ID not found in cookies for delete_task");
-            return HttpResponse::BadRequest().json(DeleteTaskResponse {
-                success: false,
-                message: "Session ID not found".to_string(),
-            });
-        }
-    };
-
-    let session_result = sqlx::query!(
-        "SELECT u.user_name FROM Sessions_ s
-         JOIN Users_ u ON s.user_id = u.user_id
-         WHERE s.session_id = ? AND s.expires_at > NOW()",
-        session_id
-    )
-    .fetch_one(pool.get_ref())
-    .await;
-
-    let current_user_name = match session_result {
-        Ok(session) => session.user_name,
-        Err(_) => {
-            info!("Invalid or expired session ID: {}", session_id);
-            return HttpResponse::BadRequest().json(DeleteTaskResponse {
+    // Check that the current user belongs to the group
+    let membership = match require_group_member(pool.get_ref(), &current_user, owner_user_name, group_name).await {
+        Ok(membership) => membership,
+        Err(e) => {
+            info!("Group access denied for {} on {}: {}", current_user.user_name, group_name, e);
+            return HttpResponse::build(e.status_code()).json(DeleteTaskResponse {
                 success: false,
-                message: "Invalid or expired session ID".to_string(),
+                message: e.to_string(),
             });
         }
     };
+    let group_id = membership.group_id;
 
-    // Assert owner_user_name == current user name
-    if owner_user_name != &current_user_name {
-        return HttpResponse::BadRequest().json(DeleteTaskResponse {
+    // Check that the role of the member allows managing tasks
+    if !membership.can(Permission::ManageTasks) {
+        info!("{} ({}) is not allowed to manage tasks of {}", current_user.user_name, membership.role.as_str(), group_name);
+        return HttpResponse::Forbidden().json(DeleteTaskResponse {
             success: false,
-            message: "Unauthorized action".to_string(),
+            message: "Your role in this group does not allow this action".to_string(),
         });
     }
 
-    // Get group_id using group_name from Groups_
-    let group_id_result = sqlx::query!(
-        "
-        SELECT g.group_id 
-        FROM Groups_ g
-        JOIN Users_ u ON g.owner_user_id = u.user_id
-        WHERE g.group_name = ? AND u.user_name = ?
-        ",
-        group_name, owner_user_name
-    )
-    .fetch_one(pool.get_ref())
-    .await;
-
-    let group_id = match group_id_result {
-        Ok(record) => record.group_id,
-        Err(_) => {
-            info!("Group not found: {}", group_name);
-            return HttpResponse::BadRequest().json(DeleteTaskResponse {
-                success: false,
-                message: "Group not found".to_string(),
-            });
-        }
-    };
-
     // Get project_id using project_name from Projects_
     let project_id_result = sqlx::query!(
         "
@@ -1232,6 +2203,18 @@ pub async fn delete_task(
         }
     };
 
+    // The subtasks of the task are deleted with it
+    let task_ids = match fetch_task_parents(pool.get_ref(), project_id).await {
+        Ok(parents) => subtree(task_id, &children_of(&parents)),
+        Err(e) => {
+            error!("Failed to fetch task hierarchy for project {}: {}", project_id, e);
+            return HttpResponse::InternalServerError().json(DeleteTaskResponse {
+                success: false,
+                message: "Failed to fetch subtasks".to_string(),
+            });
+        }
+    };
+
     // Begin a transaction
     let mut tx = match pool.begin().await {
         Ok(transaction) => transaction,
@@ -1244,7 +2227,50 @@ pub async fn delete_task(
         }
     };
 
-    // Delete the task
+    // Delete dependencies in which the task or one of its subtasks is either end,
+    // and note their attachment files, whose rows go with the tasks
+    let mut storage_names: Vec<String> = Vec::new();
+    for deleted_task_id in &task_ids {
+        let delete_dependencies_result = sqlx::query!(
+            "
+            DELETE FROM Dependencies_ 
+            WHERE prev_task_id = ? OR next_task_id = ?
+            ",
+            deleted_task_id, deleted_task_id
+        )
+        .execute(&mut *tx)
+        .await;
+
+        if let Err(e) = delete_dependencies_result {
+            error!("Failed to delete dependencies for task {}: {}", deleted_task_id, e);
+            let _ = tx.rollback().await;
+            return HttpResponse::InternalServerError().json(DeleteTaskResponse {
+                success: false,
+                message: "Failed to delete dependencies".to_string(),
+            });
+        }
+
+        let attachments_result = sqlx::query_scalar!(
+            "SELECT storage_name FROM Attachments_ WHERE task_id = ?",
+            deleted_task_id
+        )
+        .fetch_all(&mut *tx)
+        .await;
+
+        match attachments_result {
+            Ok(names) => storage_names.extend(names),
+            Err(e) => {
+                error!("Failed to fetch attachments of task {}: {}", deleted_task_id, e);
+                let _ = tx.rollback().await;
+                return HttpResponse::InternalServerError().json(DeleteTaskResponse {
+                    success: false,
+                    message: "Failed to fetch attachments".to_string(),
+                });
+            }
+        }
+    }
+
+    // Delete the task, its subtasks follow through the parent_task_id foreign key
     let delete_task_result = sqlx::query!(
         "
         DELETE FROM Tasks_ 
@@ -1273,8 +2299,1409 @@ pub async fn delete_task(
         });
     }
 
+    remove_attachment_files(&storage_names).await;
+
     HttpResponse::Ok().json(DeleteTaskResponse {
         success: true,
-        message: "Task deleted successfully".to_string(),
+        message: match task_ids.len() - 1 {
+            0 => "Task deleted successfully".to_string(),
+            subtask_count => format!("Task and {} subtasks deleted successfully", subtask_count),
+        },
     })
-}
\ No newline at end of file
+}
+
+// Handler to get the dependency edges of a project
+pub async fn get_dependency_list(
+    pool: web::Data<MySqlPool>,
+    current_user: CurrentUser,
+    request: web::Json<GetDependencyListRequest>,
+) -> impl Responder {
+    let owner_user_name = &request.owner_user_name;
+    let group_name = &request.group_name;
+    let project_name = &request.project_name;
+
+    // Check that the current user belongs to the group
+    let group_id = match require_group_member(pool.get_ref(), &current_user, owner_user_name, group_name).await {
+        Ok(membership) => membership.group_id,
+        Err(e) => {
+            info!("Group access denied for {} on {}: {}", current_user.user_name, group_name, e);
+            return HttpResponse::build(e.status_code()).json(GetDependencyListResponse { dependencies: Vec::new() });
+        }
+    };
+
+    // Get project_id using project_name from Projects_
+    let project_id_result = sqlx::query!(
+        "
+        SELECT p.project_id 
+        FROM Projects_ p
+        WHERE p.group_id = ? AND p.project_name = ?
+        ",
+        group_id, project_name
+    )
+    .fetch_one(pool.get_ref())
+    .await;
+
+    let project_id = match project_id_result {
+        Ok(record) => record.project_id,
+        Err(_) => {
+            info!("Project not found: {}", project_name);
+            return HttpResponse::BadRequest().json(GetDependencyListResponse { dependencies: Vec::new() });
+        }
+    };
+
+    // Get dependency edges between the tasks of the project
+    let dependencies_result = sqlx::query!(
+        "
+        SELECT pt.title AS prev_task_title, nt.title AS next_task_title
+        FROM Dependencies_ d
+        JOIN Tasks_ pt ON d.prev_task_id = pt.task_id
+        JOIN Tasks_ nt ON d.next_task_id = nt.task_id
+        WHERE pt.project_id = ?
+        ",
+        project_id
+    )
+    .fetch_all(pool.get_ref())
+    .await;
+
+    match dependencies_result {
+        Ok(records) => {
+            let dependencies: Vec<Dependency> = records.into_iter().map(|record| Dependency {
+                prev_task_title: record.prev_task_title,
+                next_task_title: record.next_task_title,
+            }).collect();
+
+            HttpResponse::Ok().json(GetDependencyListResponse { dependencies })
+        },
+        Err(e) => {
+            error!("Failed to fetch dependencies for project_id {}: {}", project_id, e);
+            HttpResponse::InternalServerError().json(GetDependencyListResponse { dependencies: Vec::new() })
+        }
+    }
+}
+
+pub async fn add_dependency(
+    pool: web::Data<MySqlPool>,
+    current_user: CurrentUser,
+    request: web::Json<AddDependencyRequest>,
+) -> impl Responder {
+    let owner_user_name = &request.owner_user_name;
+    let group_name = &request.group_name;
+    let project_name = &request.project_name;
+    let prev_task_title = &request.prev_task_title;
+    let next_task_title = &request.next_task_title;
+
+    // A task cannot depend on itself
+    if prev_task_title == next_task_title {
+        return HttpResponse::BadRequest().json(AddDependencyResponse {
+            success: false,
+            message: "A task cannot depend on itself".to_string(),
+        });
+    }
+
+    // Check that the current user belongs to the group
+    let membership = match require_group_member(pool.get_ref(), &current_user, owner_user_name, group_name).await {
+        Ok(membership) => membership,
+        Err(e) => {
+            info!("Group access denied for {} on {}: {}", current_user.user_name, group_name, e);
+            return HttpResponse::build(e.status_code()).json(AddDependencyResponse {
+                success: false,
+                message: e.to_string(),
+            });
+        }
+    };
+    let group_id = membership.group_id;
+
+    // Check that the role of the member allows managing tasks
+    if !membership.can(Permission::ManageTasks) {
+        info!("{} ({}) is not allowed to manage tasks of {}", current_user.user_name, membership.role.as_str(), group_name);
+        return HttpResponse::Forbidden().json(AddDependencyResponse {
+            success: false,
+            message: "Your role in this group does not allow this action".to_string(),
+        });
+    }
+
+    // Get project_id using project_name from Projects_
+    let project_id_result = sqlx::query!(
+        "
+        SELECT p.project_id 
+        FROM Projects_ p
+        WHERE p.group_id = ? AND p.project_name = ?
+        ",
+        group_id, project_name
+    )
+    .fetch_one(pool.get_ref())
+    .await;
+
+    let project_id = match project_id_result {
+        Ok(record) => record.project_id,
+        Err(_) => {
+            info!("Project not found: {}", project_name);
+            return HttpResponse::BadRequest().json(AddDependencyResponse {
+                success: false,
+                message: "Project not found".to_string(),
+            });
+        }
+    };
+
+    // Get the task_id of both ends of the dependency
+    let prev_task_id_result = sqlx::query!(
+        "
+        SELECT t.task_id 
+        FROM Tasks_ t
+        WHERE t.project_id = ? AND t.title = ?
+        ",
+        project_id, prev_task_title
+    )
+    .fetch_one(pool.get_ref())
+    .await;
+
+    let prev_task_id = match prev_task_id_result {
+        Ok(record) => record.task_id,
+        Err(_) => {
+            info!("Task not found: {}", prev_task_title);
+            return HttpResponse::BadRequest().json(AddDependencyResponse {
+                success: false,
+                message: "Previous task not found".to_string(),
+            });
+        }
+    };
+
+    let next_task_id_result = sqlx::query!(
+        "
+        SELECT t.task_id 
+        FROM Tasks_ t
+        WHERE t.project_id = ? AND t.title = ?
+        ",
+        project_id, next_task_title
+    )
+    .fetch_one(pool.get_ref())
+    .await;
+
+    let next_task_id = match next_task_id_result {
+        Ok(record) => record.task_id,
+        Err(_) => {
+            info!("Task not found: {}", next_task_title);
+            return HttpResponse::BadRequest().json(AddDependencyResponse {
+                success: false,
+                message: "Next task not found".to_string(),
+            });
+        }
+    };
+
+    // Begin a transaction
+    let mut tx = match pool.begin().await {
+        Ok(transaction) => transaction,
+        Err(e) => {
+            error!("Failed to start transaction: {}", e);
+            return HttpResponse::InternalServerError().json(AddDependencyResponse {
+                success: false,
+                message: "Failed to start transaction".to_string(),
+            });
+        }
+    };
+
+    // Validate against the schedule as it stands once other changes to the project are done
+    if let Err(e) = lock_project_schedule(&mut tx, project_id).await {
+        error!("Failed to lock project {}: {}", project_id, e);
+        let _ = tx.rollback().await;
+        return HttpResponse::InternalServerError().json(AddDependencyResponse {
+            success: false,
+            message: "Failed to fetch project schedule".to_string(),
+        });
+    }
+
+    // Validate the new edge against the current project schedule
+    let (tasks, mut edges) = match fetch_project_schedule(&mut tx, project_id).await {
+        Ok(schedule) => schedule,
+        Err(e) => {
+            error!("Failed to fetch schedule for project {}: {}", project_id, e);
+            let _ = tx.rollback().await;
+            return HttpResponse::InternalServerError().json(AddDependencyResponse {
+                success: false,
+                message: "Failed to fetch project schedule".to_string(),
+            });
+        }
+    };
+
+    if edges.contains(&(prev_task_id, next_task_id)) {
+        let _ = tx.rollback().await;
+        return HttpResponse::BadRequest().json(AddDependencyResponse {
+            success: false,
+            message: "Dependency already exists".to_string(),
+        });
+    }
+
+    edges.push((prev_task_id, next_task_id));
+
+    if let Some(cycle) = find_cycle(&tasks, &edges) {
+        info!("Rejected dependency {} -> {} creating a cycle: {:?}", prev_task_id, next_task_id, cycle);
+        let _ = tx.rollback().await;
+        return HttpResponse::BadRequest().json(AddDependencyResponse {
+            success: false,
+            message: describe_cycle(&cycle),
+        });
+    }
+
+    let conflicts = find_schedule_conflicts(&tasks, &[(prev_task_id, next_task_id)], None);
+    if !conflicts.is_empty() {
+        info!("Rejected dependency {} -> {} with schedule conflicts", prev_task_id, next_task_id);
+        let _ = tx.rollback().await;
+        return HttpResponse::BadRequest().json(AddDependencyResponse {
+            success: false,
+            message: describe_conflicts(&conflicts),
+        });
+    }
+
+    // Add dependency to Dependencies_
+    let insert_result = sqlx::query!(
+        "INSERT INTO Dependencies_ (prev_task_id, next_task_id) VALUES (?, ?)",
+        prev_task_id, next_task_id
+    )
+    .execute(&mut *tx)
+    .await;
+
+    if let Err(e) = insert_result {
+        error!("Failed to add dependency {} -> {}: {}", prev_task_id, next_task_id, e);
+        let _ = tx.rollback().await;
+        return HttpResponse::InternalServerError().json(AddDependencyResponse {
+            success: false,
+            message: "Failed to add dependency".to_string(),
+        });
+    }
+
+    // Commit the transaction
+    if let Err(e) = tx.commit().await {
+        error!("Failed to commit transaction: {}", e);
+        return HttpResponse::InternalServerError().json(AddDependencyResponse {
+            success: false,
+            message: "Failed to commit transaction".to_string(),
+        });
+    }
+
+    HttpResponse::Ok().json(AddDependencyResponse {
+        success: true,
+        message: "Dependency added successfully".to_string(),
+    })
+}
+
+pub async fn delete_dependency(
+    pool: web::Data<MySqlPool>,
+    current_user: CurrentUser,
+    request: web::Json<DeleteDependencyRequest>,
+) -> impl Responder {
+    let owner_user_name = &request.owner_user_name;
+    let group_name = &request.group_name;
+    let project_name = &request.project_name;
+    let prev_task_title = &request.prev_task_title;
+    let next_task_title = &request.next_task_title;
+
+    // Check that the current user belongs to the group
+    let membership = match require_group_member(pool.get_ref(), &current_user, owner_user_name, group_name).await {
+        Ok(membership) => membership,
+        Err(e) => {
+            info!("Group access denied for {} on {}: {}", current_user.user_name, group_name, e);
+            return HttpResponse::build(e.status_code()).json(DeleteDependencyResponse {
+                success: false,
+                message: e.to_string(),
+            });
+        }
+    };
+    let group_id = membership.group_id;
+
+    // Check that the role of the member allows managing tasks
+    if !membership.can(Permission::ManageTasks) {
+        info!("{} ({}) is not allowed to manage tasks of {}", current_user.user_name, membership.role.as_str(), group_name);
+        return HttpResponse::Forbidden().json(DeleteDependencyResponse {
+            success: false,
+            message: "Your role in this group does not allow this action".to_string(),
+        });
+    }
+
+    // Get project_id using project_name from Projects_
+    let project_id_result = sqlx::query!(
+        "
+        SELECT p.project_id 
+        FROM Projects_ p
+        WHERE p.group_id = ? AND p.project_name = ?
+        ",
+        group_id, project_name
+    )
+    .fetch_one(pool.get_ref())
+    .await;
+
+    let project_id = match project_id_result {
+        Ok(record) => record.project_id,
+        Err(_) => {
+            info!("Project not found: {}", project_name);
+            return HttpResponse::BadRequest().json(DeleteDependencyResponse {
+                success: false,
+                message: "Project not found".to_string(),
+            });
+        }
+    };
+
+    // Remove the dependency between the two tasks of the project
+    let delete_result = sqlx::query!(
+        "
+        DELETE d FROM Dependencies_ d
+        JOIN Tasks_ pt ON d.prev_task_id = pt.task_id
+        JOIN Tasks_ nt ON d.next_task_id = nt.task_id
+        WHERE pt.project_id = ? AND pt.title = ? AND nt.project_id = ? AND nt.title = ?
+        ",
+        project_id, prev_task_title, project_id, next_task_title
+    )
+    .execute(pool.get_ref())
+    .await;
+
+    match delete_result {
+        Ok(result) if result.rows_affected() == 0 => {
+            info!("Dependency not found: {} -> {}", prev_task_title, next_task_title);
+            HttpResponse::BadRequest().json(DeleteDependencyResponse {
+                success: false,
+                message: "Dependency not found".to_string(),
+            })
+        },
+        Ok(_) => HttpResponse::Ok().json(DeleteDependencyResponse {
+            success: true,
+            message: "Dependency deleted successfully".to_string(),
+        }),
+        Err(e) => {
+            error!("Failed to delete dependency {} -> {}: {}", prev_task_title, next_task_title, e);
+            HttpResponse::InternalServerError().json(DeleteDependencyResponse {
+                success: false,
+                message: "Failed to delete dependency".to_string(),
+            })
+        }
+    }
+}
+
+// Find a task of the group by project name and title
+async fn find_task_id(
+    pool: &MySqlPool,
+    group_id: i32,
+    project_name: &str,
+    task_title: &str,
+) -> Result<Option<i32>, sqlx::Error> {
+    let record = sqlx::query!(
+        "SELECT t.task_id
+         FROM Tasks_ t
+         JOIN Projects_ p ON t.project_id = p.project_id
+         WHERE p.group_id = ? AND p.project_name = ? AND t.title = ?",
+        group_id, project_name, task_title
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(record.map(|record| record.task_id))
+}
+
+// Find the author, task id and task title of a comment made on a task of the group
+async fn find_comment(pool: &MySqlPool, group_id: i32, comment_id: i32) -> Result<Option<(i32, i32, String)>, sqlx::Error> {
+    let record = sqlx::query!(
+        "SELECT c.author_user_id, c.task_id, t.title
+         FROM TaskComments_ c
+         JOIN Tasks_ t ON c.task_id = t.task_id
+         JOIN Projects_ p ON t.project_id = p.project_id
+         WHERE p.group_id = ? AND c.comment_id = ?",
+        group_id, comment_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(record.map(|record| (record.author_user_id, record.task_id, record.title)))
+}
+
+// Record the group members mentioned in a comment and notify those not mentioned before.
+// Names that are not members of the group stay plain text. Returns the names of the notified members.
+async fn record_mentions(
+    tx: &mut sqlx::Transaction<'_, sqlx::MySql>,
+    group_id: i32,
+    comment_id: i32,
+    task_id: i32,
+    task_title: &str,
+    author: &CurrentUser,
+    content: &str,
+) -> Result<Vec<String>, sqlx::Error> {
+    let previous_user_ids: Vec<i32> = sqlx::query_scalar!(
+        "SELECT user_id FROM CommentMentions_ WHERE comment_id = ?",
+        comment_id
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    sqlx::query!("DELETE FROM CommentMentions_ WHERE comment_id = ?", comment_id)
+        .execute(&mut **tx)
+        .await?;
+
+    let mut notified = Vec::new();
+    for user_name in parse_mentions(content) {
+        let member = sqlx::query!(
+            "SELECT u.user_id
+             FROM GroupUserMapping_ gum
+             JOIN Users_ u ON gum.user_id = u.user_id
+             WHERE gum.group_id = ? AND u.user_name = ?",
+            group_id, user_name
+        )
+        .fetch_optional(&mut **tx)
+        .await?;
+
+        let user_id = match member {
+            Some(record) => record.user_id,
+            None => continue,
+        };
+
+        sqlx::query!(
+            "INSERT IGNORE INTO CommentMentions_ (comment_id, user_id) VALUES (?, ?)",
+            comment_id, user_id
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        if user_id != author.user_id && !previous_user_ids.contains(&user_id) {
+            let message = format!("{} mentioned you on task '{}'", author.user_name, task_title);
+            create_notification(&mut **tx, user_id, NotificationKind::Mention, &message, Some(group_id), Some(task_id)).await?;
+            notified.push(user_name);
+        }
+    }
+
+    Ok(notified)
+}
+
+// Handler to get a page of the comments on a task, oldest first
+pub async fn get_comment_list(
+    pool: web::Data<MySqlPool>,
+    current_user: CurrentUser,
+    request: web::Json<GetCommentListRequest>,
+) -> impl Responder {
+    let owner_user_name = &request.owner_user_name;
+    let group_name = &request.group_name;
+    let project_name = &request.project_name;
+    let task_title = &request.task_title;
+    let offset = request.offset;
+    let limit = request.limit.unwrap_or(COMMENT_PAGE_SIZE).clamp(1, MAX_COMMENT_PAGE_SIZE);
+
+    // Check that the current user belongs to the group
+    let group_id = match require_group_member(pool.get_ref(), &current_user, owner_user_name, group_name).await {
+        Ok(membership) => membership.group_id,
+        Err(e) => {
+            info!("Group access denied for {} on {}: {}", current_user.user_name, group_name, e);
+            return HttpResponse::build(e.status_code()).json(GetCommentListResponse { comments: Vec::new(), total_count: 0 });
+        }
+    };
+
+    let task_id = match find_task_id(pool.get_ref(), group_id, project_name, task_title).await {
+        Ok(Some(task_id)) => task_id,
+        Ok(None) => {
+            info!("Task not found: {}/{}", project_name, task_title);
+            return HttpResponse::BadRequest().json(GetCommentListResponse { comments: Vec::new(), total_count: 0 });
+        }
+        Err(e) => {
+            error!("Failed to fetch task {}/{}: {}", project_name, task_title, e);
+            return HttpResponse::InternalServerError().json(GetCommentListResponse { comments: Vec::new(), total_count: 0 });
+        }
+    };
+
+    let total_count = match sqlx::query!("SELECT COUNT(*) AS count FROM TaskComments_ WHERE task_id = ?", task_id)
+        .fetch_one(pool.get_ref())
+        .await
+    {
+        Ok(record) => record.count,
+        Err(e) => {
+            error!("Failed to count comments of task {}: {}", task_id, e);
+            return HttpResponse::InternalServerError().json(GetCommentListResponse { comments: Vec::new(), total_count: 0 });
+        }
+    };
+
+    let comments_result = sqlx::query!(
+        "SELECT c.comment_id, u.user_name AS author_name, c.content, c.created_at, c.updated_at
+         FROM TaskComments_ c
+         JOIN Users_ u ON c.author_user_id = u.user_id
+         WHERE c.task_id = ?
+         ORDER BY c.created_at, c.comment_id
+         LIMIT ? OFFSET ?",
+        task_id, limit, offset
+    )
+    .fetch_all(pool.get_ref())
+    .await;
+
+    match comments_result {
+        Ok(records) => {
+            let comments: Vec<Comment> = records.into_iter().map(|record| Comment {
+                comment_id: record.comment_id,
+                author_name: record.author_name,
+                content: record.content,
+                created_at: record.created_at.to_string(),
+                updated_at: record.updated_at.map(|updated_at| updated_at.to_string()),
+            }).collect();
+
+            HttpResponse::Ok().json(GetCommentListResponse { comments, total_count })
+        }
+        Err(e) => {
+            error!("Failed to fetch comments of task {}: {}", task_id, e);
+            HttpResponse::InternalServerError().json(GetCommentListResponse { comments: Vec::new(), total_count: 0 })
+        }
+    }
+}
+
+// Handler to comment on a task
+pub async fn add_comment(
+    pool: web::Data<MySqlPool>,
+    current_user: CurrentUser,
+    request: web::Json<AddCommentRequest>,
+) -> impl Responder {
+    let owner_user_name = &request.owner_user_name;
+    let group_name = &request.group_name;
+    let project_name = &request.project_name;
+    let task_title = &request.task_title;
+    let content = request.content.trim();
+
+    if content.is_empty() {
+        return HttpResponse::BadRequest().json(AddCommentResponse {
+            success: false,
+            message: "Comment cannot be empty".to_string(),
+            comment_id: 0,
+        });
+    }
+
+    // Check that the current user belongs to the group
+    let membership = match require_group_member(pool.get_ref(), &current_user, owner_user_name, group_name).await {
+        Ok(membership) => membership,
+        Err(e) => {
+            info!("Group access denied for {} on {}: {}", current_user.user_name, group_name, e);
+            return HttpResponse::build(e.status_code()).json(AddCommentResponse {
+                success: false,
+                message: e.to_string(),
+                comment_id: 0,
+            });
+        }
+    };
+    let group_id = membership.group_id;
+
+    // Check that the role of the member allows commenting
+    if !membership.can(Permission::CommentTasks) {
+        info!("{} ({}) is not allowed to comment on tasks of {}", current_user.user_name, membership.role.as_str(), group_name);
+        return HttpResponse::Forbidden().json(AddCommentResponse {
+            success: false,
+            message: "Your role in this group does not allow this action".to_string(),
+            comment_id: 0,
+        });
+    }
+
+    let task_id = match find_task_id(pool.get_ref(), group_id, project_name, task_title).await {
+        Ok(Some(task_id)) => task_id,
+        Ok(None) => {
+            info!("Task not found: {}/{}", project_name, task_title);
+            return HttpResponse::BadRequest().json(AddCommentResponse {
+                success: false,
+                message: "Task not found".to_string(),
+                comment_id: 0,
+            });
+        }
+        Err(e) => {
+            error!("Failed to fetch task {}/{}: {}", project_name, task_title, e);
+            return HttpResponse::InternalServerError().json(AddCommentResponse {
+                success: false,
+                message: "Failed to fetch task".to_string(),
+                comment_id: 0,
+            });
+        }
+    };
+
+    // Begin a transaction
+    let mut tx = match pool.begin().await {
+        Ok(transaction) => transaction,
+        Err(e) => {
+            error!("Failed to start transaction: {}", e);
+            return HttpResponse::InternalServerError().json(AddCommentResponse {
+                success: false,
+                message: "Failed to start transaction".to_string(),
+                comment_id: 0,
+            });
+        }
+    };
+
+    let insert_result = sqlx::query!(
+        "INSERT INTO TaskComments_ (task_id, author_user_id, content) VALUES (?, ?, ?)",
+        task_id, current_user.user_id, content
+    )
+    .execute(&mut *tx)
+    .await;
+
+    let comment_id = match insert_result {
+        Ok(result) => result.last_insert_id() as i32,
+        Err(e) => {
+            error!("Failed to add comment to task {}: {}", task_id, e);
+            let _ = tx.rollback().await;
+            return HttpResponse::InternalServerError().json(AddCommentResponse {
+                success: false,
+                message: "Failed to add comment".to_string(),
+                comment_id: 0,
+            });
+        }
+    };
+
+    // Record the mentions of the comment and notify the mentioned members
+    match record_mentions(&mut tx, group_id, comment_id, task_id, task_title, &current_user, content).await {
+        Ok(notified) if !notified.is_empty() => info!("Comment {} mentioned {:?}", comment_id, notified),
+        Ok(_) => {}
+        Err(e) => {
+            error!("Failed to record mentions of comment {}: {}", comment_id, e);
+            let _ = tx.rollback().await;
+            return HttpResponse::InternalServerError().json(AddCommentResponse {
+                success: false,
+                message: "Failed to add comment".to_string(),
+                comment_id: 0,
+            });
+        }
+    }
+
+    if let Err(e) = tx.commit().await {
+        error!("Failed to commit transaction: {}", e);
+        return HttpResponse::InternalServerError().json(AddCommentResponse {
+            success: false,
+            message: "Failed to commit transaction".to_string(),
+            comment_id: 0,
+        });
+    }
+
+    HttpResponse::Ok().json(AddCommentResponse {
+        success: true,
+        message: "Comment added successfully".to_string(),
+        comment_id,
+    })
+}
+
+// Handler to edit a comment, only its author may do so
+pub async fn update_comment(
+    pool: web::Data<MySqlPool>,
+    current_user: CurrentUser,
+    request: web::Json<UpdateCommentRequest>,
+) -> impl Responder {
+    let owner_user_name = &request.owner_user_name;
+    let group_name = &request.group_name;
+    let comment_id = request.comment_id;
+    let new_content = request.new_content.trim();
+
+    if new_content.is_empty() {
+        return HttpResponse::BadRequest().json(UpdateCommentResponse {
+            success: false,
+            message: "Comment cannot be empty".to_string(),
+        });
+    }
+
+    // Check that the current user belongs to the group
+    let group_id = match require_group_member(pool.get_ref(), &current_user, owner_user_name, group_name).await {
+        Ok(membership) => membership.group_id,
+        Err(e) => {
+            info!("Group access denied for {} on {}: {}", current_user.user_name, group_name, e);
+            return HttpResponse::build(e.status_code()).json(UpdateCommentResponse {
+                success: false,
+                message: e.to_string(),
+            });
+        }
+    };
+
+    let (task_id, task_title) = match find_comment(pool.get_ref(), group_id, comment_id).await {
+        Ok(Some((author_user_id, task_id, task_title))) if author_user_id == current_user.user_id => (task_id, task_title),
+        Ok(Some(_)) => {
+            info!("{} tried to edit comment {} of another user", current_user.user_name, comment_id);
+            return HttpResponse::Forbidden().json(UpdateCommentResponse {
+                success: false,
+                message: "Only the author can edit a comment".to_string(),
+            });
+        }
+        Ok(None) => {
+            return HttpResponse::BadRequest().json(UpdateCommentResponse {
+                success: false,
+                message: "Comment not found".to_string(),
+            });
+        }
+        Err(e) => {
+            error!("Failed to fetch comment {}: {}", comment_id, e);
+            return HttpResponse::InternalServerError().json(UpdateCommentResponse {
+                success: false,
+                message: "Failed to fetch comment".to_string(),
+            });
+        }
+    };
+
+    // Begin a transaction
+    let mut tx = match pool.begin().await {
+        Ok(transaction) => transaction,
+        Err(e) => {
+            error!("Failed to start transaction: {}", e);
+            return HttpResponse::InternalServerError().json(UpdateCommentResponse {
+                success: false,
+                message: "Failed to start transaction".to_string(),
+            });
+        }
+    };
+
+    let update_result = sqlx::query!(
+        "UPDATE TaskComments_ SET content = ?, updated_at = CURRENT_TIMESTAMP WHERE comment_id = ?",
+        new_content, comment_id
+    )
+    .execute(&mut *tx)
+    .await;
+
+    if let Err(e) = update_result {
+        error!("Failed to update comment {}: {}", comment_id, e);
+        let _ = tx.rollback().await;
+        return HttpResponse::InternalServerError().json(UpdateCommentResponse {
+            success: false,
+            message: "Failed to update comment".to_string(),
+        });
+    }
+
+    // Members newly mentioned by the edit are notified, mentions removed from the text are dropped
+    match record_mentions(&mut tx, group_id, comment_id, task_id, &task_title, &current_user, new_content).await {
+        Ok(notified) if !notified.is_empty() => info!("Comment {} mentioned {:?}", comment_id, notified),
+        Ok(_) => {}
+        Err(e) => {
+            error!("Failed to record mentions of comment {}: {}", comment_id, e);
+            let _ = tx.rollback().await;
+            return HttpResponse::InternalServerError().json(UpdateCommentResponse {
+                success: false,
+                message: "Failed to update comment".to_string(),
+            });
+        }
+    }
+
+    if let Err(e) = tx.commit().await {
+        error!("Failed to commit transaction: {}", e);
+        return HttpResponse::InternalServerError().json(UpdateCommentResponse {
+            success: false,
+            message: "Failed to commit transaction".to_string(),
+        });
+    }
+
+    HttpResponse::Ok().json(UpdateCommentResponse {
+        success: true,
+        message: "Comment updated successfully".to_string(),
+    })
+}
+
+// Handler to delete a comment, by its author or by a member who can manage members
+pub async fn delete_comment(
+    pool: web::Data<MySqlPool>,
+    current_user: CurrentUser,
+    request: web::Json<DeleteCommentRequest>,
+) -> impl Responder {
+    let owner_user_name = &request.owner_user_name;
+    let group_name = &request.group_name;
+    let comment_id = request.comment_id;
+
+    // Check that the current user belongs to the group
+    let membership = match require_group_member(pool.get_ref(), &current_user, owner_user_name, group_name).await {
+        Ok(membership) => membership,
+        Err(e) => {
+            info!("Group access denied for {} on {}: {}", current_user.user_name, group_name, e);
+            return HttpResponse::build(e.status_code()).json(DeleteCommentResponse {
+                success: false,
+                message: e.to_string(),
+            });
+        }
+    };
+    let group_id = membership.group_id;
+
+    match find_comment(pool.get_ref(), group_id, comment_id).await {
+        Ok(Some((author_user_id, _, _))) if author_user_id == current_user.user_id || membership.can(Permission::ManageMembers) => {}
+        Ok(Some(_)) => {
+            info!("{} ({}) is not allowed to delete comment {}", current_user.user_name, membership.role.as_str(), comment_id);
+            return HttpResponse::Forbidden().json(DeleteCommentResponse {
+                success: false,
+                message: "Your role in this group does not allow this action".to_string(),
+            });
+        }
+        Ok(None) => {
+            return HttpResponse::BadRequest().json(DeleteCommentResponse {
+                success: false,
+                message: "Comment not found".to_string(),
+            });
+        }
+        Err(e) => {
+            error!("Failed to fetch comment {}: {}", comment_id, e);
+            return HttpResponse::InternalServerError().json(DeleteCommentResponse {
+                success: false,
+                message: "Failed to fetch comment".to_string(),
+            });
+        }
+    }
+
+    let delete_result = sqlx::query!("DELETE FROM TaskComments_ WHERE comment_id = ?", comment_id)
+        .execute(pool.get_ref())
+        .await;
+
+    match delete_result {
+        Ok(_) => HttpResponse::Ok().json(DeleteCommentResponse {
+            success: true,
+            message: "Comment deleted successfully".to_string(),
+        }),
+        Err(e) => {
+            error!("Failed to delete comment {}: {}", comment_id, e);
+            HttpResponse::InternalServerError().json(DeleteCommentResponse {
+                success: false,
+                message: "Failed to delete comment".to_string(),
+            })
+        }
+    }
+}
+
+// Quota of the group and total size of its attachments
+async fn fetch_attachment_usage(pool: &MySqlPool, group_id: i32) -> Result<(i64, i64), sqlx::Error> {
+    let record = sqlx::query!(
+        "SELECT g.attachment_quota_bytes,
+                CAST((SELECT COALESCE(SUM(a.size_bytes), 0) FROM Attachments_ a WHERE a.group_id = g.group_id) AS SIGNED) AS `used_bytes!`
+         FROM Groups_ g
+         WHERE g.group_id = ?",
+        group_id
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok((record.attachment_quota_bytes, record.used_bytes))
+}
+
+// Handler to upload a file attached to a project or one of its tasks
+pub async fn upload_attachment(
+    pool: web::Data<MySqlPool>,
+    current_user: CurrentUser,
+    mut payload: Multipart,
+) -> impl Responder {
+    // Text fields come first, the file last
+    let mut fields: HashMap<String, String> = HashMap::new();
+    while let Some(item) = payload.next().await {
+        let mut field = match item {
+            Ok(field) => field,
+            Err(e) => {
+                info!("Invalid upload from {}: {}", current_user.user_name, e);
+                return HttpResponse::BadRequest().json(UploadAttachmentResponse {
+                    success: false,
+                    message: "Invalid multipart body".to_string(),
+                    attachment_id: 0,
+                });
+            }
+        };
+
+        let name = field.name().unwrap_or_default().to_string();
+        if name == "file" {
+            return store_attachment(pool.get_ref(), &current_user, &fields, field).await;
+        }
+
+        let mut value = Vec::new();
+        while let Some(chunk) = field.next().await {
+            match chunk {
+                Ok(bytes) if value.len() + bytes.len() <= MAX_UPLOAD_FIELD_BYTES => value.extend_from_slice(&bytes),
+                _ => {
+                    return HttpResponse::BadRequest().json(UploadAttachmentResponse {
+                        success: false,
+                        message: format!("Invalid field: {}", name),
+                        attachment_id: 0,
+                    });
+                }
+            }
+        }
+        fields.insert(name, String::from_utf8_lossy(&value).into_owned());
+    }
+
+    HttpResponse::BadRequest().json(UploadAttachmentResponse {
+        success: false,
+        message: "No file uploaded".to_string(),
+        attachment_id: 0,
+    })
+}
+
+// Check the upload against the group and its quota, then write the file and record it
+async fn store_attachment(
+    pool: &MySqlPool,
+    current_user: &CurrentUser,
+    fields: &HashMap<String, String>,
+    mut field: Field,
+) -> HttpResponse {
+    let field_value = |name: &str| fields.get(name).map(String::as_str).unwrap_or_default();
+    let owner_user_name = field_value("owner_user_name");
+    let group_name = field_value("group_name");
+    let project_name = field_value("project_name");
+    let task_title = field_value("task_title");
+
+    // Check that the current user belongs to the group
+    let membership = match require_group_member(pool, current_user, owner_user_name, group_name).await {
+        Ok(membership) => membership,
+        Err(e) => {
+            info!("Group access denied for {} on {}: {}", current_user.user_name, group_name, e);
+            return HttpResponse::build(e.status_code()).json(UploadAttachmentResponse {
+                success: false,
+                message: e.to_string(),
+                attachment_id: 0,
+            });
+        }
+    };
+    let group_id = membership.group_id;
+
+    // Check that the role of the member allows managing tasks
+    if !membership.can(Permission::ManageTasks) {
+        info!("{} ({}) is not allowed to manage tasks of {}", current_user.user_name, membership.role.as_str(), group_name);
+        return HttpResponse::Forbidden().json(UploadAttachmentResponse {
+            success: false,
+            message: "Your role in this group does not allow this action".to_string(),
+            attachment_id: 0,
+        });
+    }
+
+    // Get project_id using project_name from Projects_
+    let project_id = match sqlx::query!(
+        "SELECT p.project_id FROM Projects_ p WHERE p.group_id = ? AND p.project_name = ?",
+        group_id, project_name
+    )
+    .fetch_one(pool)
+    .await
+    {
+        Ok(record) => record.project_id,
+        Err(_) => {
+            info!("Project not found: {}", project_name);
+            return HttpResponse::BadRequest().json(UploadAttachmentResponse {
+                success: false,
+                message: "Project not found".to_string(),
+                attachment_id: 0,
+            });
+        }
+    };
+
+    let task_id = if task_title.is_empty() {
+        None
+    } else {
+        match find_task_id(pool, group_id, project_name, task_title).await {
+            Ok(Some(task_id)) => Some(task_id),
+            Ok(None) => {
+                info!("Task not found: {}/{}", project_name, task_title);
+                return HttpResponse::BadRequest().json(UploadAttachmentResponse {
+                    success: false,
+                    message: "Task not found".to_string(),
+                    attachment_id: 0,
+                });
+            }
+            Err(e) => {
+                error!("Failed to fetch task {}/{}: {}", project_name, task_title, e);
+                return HttpResponse::InternalServerError().json(UploadAttachmentResponse {
+                    success: false,
+                    message: "Failed to fetch task".to_string(),
+                    attachment_id: 0,
+                });
+            }
+        }
+    };
+
+    let (quota_bytes, used_bytes) = match fetch_attachment_usage(pool, group_id).await {
+        Ok(usage) => usage,
+        Err(e) => {
+            error!("Failed to fetch attachment usage of group {}: {}", group_id, e);
+            return HttpResponse::InternalServerError().json(UploadAttachmentResponse {
+                success: false,
+                message: "Failed to fetch attachment quota".to_string(),
+                attachment_id: 0,
+            });
+        }
+    };
+
+    let file_name = clean_file_name(field.content_disposition().and_then(|cd| cd.get_filename()).unwrap_or_default());
+    // The type is given by the client; one that does not fit Attachments_.content_type is dropped
+    let content_type = field
+        .content_type()
+        .map(|mime| mime.to_string())
+        .filter(|mime| mime.len() <= MAX_CONTENT_TYPE_BYTES)
+        .unwrap_or_else(|| "application/octet-stream".to_string());
+    let storage_name = Uuid::new_v4().simple().to_string();
+    let path = attachment_path(&storage_name);
+
+    // Write the file, stopping as soon as the quota of the group is exceeded
+    if let Err(e) = tokio::fs::create_dir_all(attachment_dir()).await {
+        error!("Failed to create attachment directory: {}", e);
+        return HttpResponse::InternalServerError().json(UploadAttachmentResponse {
+            success: false,
+            message: "Failed to store attachment".to_string(),
+            attachment_id: 0,
+        });
+    }
+    let mut file = match tokio::fs::File::create(&path).await {
+        Ok(file) => file,
+        Err(e) => {
+            error!("Failed to create attachment file {}: {}", storage_name, e);
+            return HttpResponse::InternalServerError().json(UploadAttachmentResponse {
+                success: false,
+                message: "Failed to store attachment".to_string(),
+                attachment_id: 0,
+            });
+        }
+    };
+
+    let mut size_bytes: i64 = 0;
+    while let Some(chunk) = field.next().await {
+        let (response, reason) = match chunk {
+            Ok(bytes) => {
+                size_bytes += bytes.len() as i64;
+                if used_bytes + size_bytes > quota_bytes {
+                    (
+                        HttpResponse::BadRequest().json(UploadAttachmentResponse {
+                            success: false,
+                            message: format!("Attachment quota of the group exceeded ({} of {} bytes used)", used_bytes, quota_bytes),
+                            attachment_id: 0,
+                        }),
+                        "quota exceeded".to_string(),
+                    )
+                } else if let Err(e) = file.write_all(&bytes).await {
+                    (
+                        HttpResponse::InternalServerError().json(UploadAttachmentResponse {
+                            success: false,
+                            message: "Failed to store attachment".to_string(),
+                            attachment_id: 0,
+                        }),
+                        e.to_string(),
+                    )
+                } else {
+                    continue;
+                }
+            }
+            Err(e) => (
+                HttpResponse::BadRequest().json(UploadAttachmentResponse {
+                    success: false,
+                    message: "Invalid multipart body".to_string(),
+                    attachment_id: 0,
+                }),
+                e.to_string(),
+            ),
+        };
+
+        info!("Upload of {} to group {} aborted: {}", file_name, group_id, reason);
+        drop(file);
+        remove_attachment_files(&[storage_name]).await;
+        return response;
+    }
+
+    if let Err(e) = file.flush().await {
+        error!("Failed to write attachment file {}: {}", storage_name, e);
+        drop(file);
+        remove_attachment_files(&[storage_name]).await;
+        return HttpResponse::InternalServerError().json(UploadAttachmentResponse {
+            success: false,
+            message: "Failed to store attachment".to_string(),
+            attachment_id: 0,
+        });
+    }
+
+    // Record the file while holding the group row, so that concurrent uploads,
+    // each within the quota on its own, cannot exceed it together
+    let mut tx = match pool.begin().await {
+        Ok(transaction) => transaction,
+        Err(e) => {
+            error!("Failed to start transaction: {}", e);
+            remove_attachment_files(&[storage_name]).await;
+            return HttpResponse::InternalServerError().json(UploadAttachmentResponse {
+                success: false,
+                message: "Failed to start transaction".to_string(),
+                attachment_id: 0,
+            });
+        }
+    };
+
+    let quota_result = sqlx::query_scalar!(
+        "SELECT attachment_quota_bytes FROM Groups_ WHERE group_id = ? FOR UPDATE",
+        group_id
+    )
+    .fetch_one(&mut *tx)
+    .await;
+
+    // Sum after taking the lock, so the uploads committed before it are counted
+    let usage_result = match quota_result {
+        Ok(quota_bytes) => sqlx::query_scalar!(
+            "SELECT CAST(COALESCE(SUM(size_bytes), 0) AS SIGNED) AS `used_bytes!` FROM Attachments_ WHERE group_id = ?",
+            group_id
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map(|used_bytes| (quota_bytes, used_bytes)),
+        Err(e) => Err(e),
+    };
+
+    match usage_result {
+        Ok((quota_bytes, used_bytes)) if used_bytes + size_bytes > quota_bytes => {
+            info!("Upload of {} to group {} aborted: quota exceeded", file_name, group_id);
+            let _ = tx.rollback().await;
+            remove_attachment_files(&[storage_name]).await;
+            return HttpResponse::BadRequest().json(UploadAttachmentResponse {
+                success: false,
+                message: format!("Attachment quota of the group exceeded ({} of {} bytes used)", used_bytes, quota_bytes),
+                attachment_id: 0,
+            });
+        }
+        Ok(_) => {}
+        Err(e) => {
+            error!("Failed to fetch attachment usage of group {}: {}", group_id, e);
+            let _ = tx.rollback().await;
+            remove_attachment_files(&[storage_name]).await;
+            return HttpResponse::InternalServerError().json(UploadAttachmentResponse {
+                success: false,
+                message: "Failed to fetch attachment quota".to_string(),
+                attachment_id: 0,
+            });
+        }
+    }
+
+    let insert_result = sqlx::query!(
+        "INSERT INTO Attachments_ (group_id, project_id, task_id, uploader_user_id, file_name, content_type, size_bytes, storage_name)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+        group_id, project_id, task_id, current_user.user_id, file_name, content_type, size_bytes, storage_name
+    )
+    .execute(&mut *tx)
+    .await;
+
+    let attachment_id = match insert_result {
+        Ok(result) => result.last_insert_id() as i32,
+        Err(e) => {
+            error!("Failed to record attachment {}: {}", storage_name, e);
+            let _ = tx.rollback().await;
+            remove_attachment_files(&[storage_name]).await;
+            return HttpResponse::InternalServerError().json(UploadAttachmentResponse {
+                success: false,
+                message: "Failed to store attachment".to_string(),
+                attachment_id: 0,
+            });
+        }
+    };
+
+    // Commit the transaction
+    if let Err(e) = tx.commit().await {
+        error!("Failed to commit transaction: {}", e);
+        remove_attachment_files(&[storage_name]).await;
+        return HttpResponse::InternalServerError().json(UploadAttachmentResponse {
+            success: false,
+            message: "Failed to commit transaction".to_string(),
+            attachment_id: 0,
+        });
+    }
+
+    info!("{} attached {} ({} bytes) to project {}", current_user.user_name, file_name, size_bytes, project_id);
+    HttpResponse::Ok().json(UploadAttachmentResponse {
+        success: true,
+        message: "Attachment uploaded successfully".to_string(),
+        attachment_id,
+    })
+}
+
+// Handler to get the files attached to a project or one of its tasks
+pub async fn get_attachment_list(
+    pool: web::Data<MySqlPool>,
+    current_user: CurrentUser,
+    request: web::Json<GetAttachmentListRequest>,
+) -> impl Responder {
+    let owner_user_name = &request.owner_user_name;
+    let group_name = &request.group_name;
+    let project_name = &request.project_name;
+    let task_title = &request.task_title;
+
+    let empty_response = || GetAttachmentListResponse { attachments: Vec::new(), used_bytes: 0, quota_bytes: 0 };
+
+    // Check that the current user belongs to the group
+    let group_id = match require_group_member(pool.get_ref(), &current_user, owner_user_name, group_name).await {
+        Ok(membership) => membership.group_id,
+        Err(e) => {
+            info!("Group access denied for {} on {}: {}", current_user.user_name, group_name, e);
+            return HttpResponse::build(e.status_code()).json(empty_response());
+        }
+    };
+
+    let (quota_bytes, used_bytes) = match fetch_attachment_usage(pool.get_ref(), group_id).await {
+        Ok(usage) => usage,
+        Err(e) => {
+            error!("Failed to fetch attachment usage of group {}: {}", group_id, e);
+            return HttpResponse::InternalServerError().json(empty_response());
+        }
+    };
+
+    let attachments_result = sqlx::query!(
+        "SELECT a.attachment_id, a.file_name, a.content_type, a.size_bytes, t.title AS `task_title?`,
+                u.user_name AS uploader_name, a.created_at
+         FROM Attachments_ a
+         JOIN Projects_ p ON a.project_id = p.project_id
+         JOIN Users_ u ON a.uploader_user_id = u.user_id
+         LEFT JOIN Tasks_ t ON a.task_id = t.task_id
+         WHERE p.group_id = ? AND p.project_name = ? AND (? = '' OR t.title = ?)
+         ORDER BY a.created_at, a.attachment_id",
+        group_id, project_name, task_title, task_title
+    )
+    .fetch_all(pool.get_ref())
+    .await;
+
+    match attachments_result {
+        Ok(records) => {
+            let attachments: Vec<Attachment> = records.into_iter().map(|record| Attachment {
+                attachment_id: record.attachment_id,
+                file_name: record.file_name,
+                content_type: record.content_type,
+                size_bytes: record.size_bytes,
+                task_title: record.task_title,
+                uploader_name: record.uploader_name,
+                created_at: record.created_at.to_string(),
+            }).collect();
+
+            HttpResponse::Ok().json(GetAttachmentListResponse { attachments, used_bytes, quota_bytes })
+        }
+        Err(e) => {
+            error!("Failed to fetch attachments of project {}: {}", project_name, e);
+            HttpResponse::InternalServerError().json(empty_response())
+        }
+    }
+}
+
+// Handler to download an attached file
+pub async fn download_attachment(
+    pool: web::Data<MySqlPool>,
+    current_user: CurrentUser,
+    request: web::Json<DownloadAttachmentRequest>,
+) -> impl Responder {
+    let owner_user_name = &request.owner_user_name;
+    let group_name = &request.group_name;
+    let attachment_id = request.attachment_id;
+
+    // Check that the current user belongs to the group
+    let group_id = match require_group_member(pool.get_ref(), &current_user, owner_user_name, group_name).await {
+        Ok(membership) => membership.group_id,
+        Err(e) => {
+            info!("Group access denied for {} on {}: {}", current_user.user_name, group_name, e);
+            return HttpResponse::build(e.status_code()).json(DownloadAttachmentResponse {
+                success: false,
+                message: e.to_string(),
+            });
+        }
+    };
+
+    let attachment = match sqlx::query!(
+        "SELECT file_name, content_type, storage_name FROM Attachments_ WHERE attachment_id = ? AND group_id = ?",
+        attachment_id, group_id
+    )
+    .fetch_optional(pool.get_ref())
+    .await
+    {
+        Ok(Some(record)) => record,
+        Ok(None) => {
+            return HttpResponse::BadRequest().json(DownloadAttachmentResponse {
+                success: false,
+                message: "Attachment not found".to_string(),
+            });
+        }
+        Err(e) => {
+            error!("Failed to fetch attachment {}: {}", attachment_id, e);
+            return HttpResponse::InternalServerError().json(DownloadAttachmentResponse {
+                success: false,
+                message: "Failed to fetch attachment".to_string(),
+            });
+        }
+    };
+
+    // Stream the file rather than loading it in memory
+    let file = match tokio::fs::File::open(attachment_path(&attachment.storage_name)).await {
+        Ok(file) => file,
+        Err(e) => {
+            error!("Failed to open attachment file {}: {}", attachment.storage_name, e);
+            return HttpResponse::InternalServerError().json(DownloadAttachmentResponse {
+                success: false,
+                message: "Failed to read attachment".to_string(),
+            });
+        }
+    };
+    let size_bytes = match file.metadata().await {
+        Ok(metadata) => metadata.len(),
+        Err(e) => {
+            error!("Failed to read attachment file {}: {}", attachment.storage_name, e);
+            return HttpResponse::InternalServerError().json(DownloadAttachmentResponse {
+                success: false,
+                message: "Failed to read attachment".to_string(),
+            });
+        }
+    };
+
+    HttpResponse::Ok()
+        .content_type(attachment.content_type)
+        .insert_header(ContentDisposition {
+            disposition: DispositionType::Attachment,
+            parameters: vec![DispositionParam::Filename(attachment.file_name)],
+        })
+        .no_chunking(size_bytes)
+        .streaming(ReaderStream::new(file))
+}
+
+// Handler to delete an attached file
+pub async fn delete_attachment(
+    pool: web::Data<MySqlPool>,
+    current_user: CurrentUser,
+    request: web::Json<DeleteAttachmentRequest>,
+) -> impl Responder {
+    let owner_user_name = &request.owner_user_name;
+    let group_name = &request.group_name;
+    let attachment_id = request.attachment_id;
+
+    // Check that the current user belongs to the group
+    let membership = match require_group_member(pool.get_ref(), &current_user, owner_user_name, group_name).await {
+        Ok(membership) => membership,
+        Err(e) => {
+            info!("Group access denied for {} on {}: {}", current_user.user_name, group_name, e);
+            return HttpResponse::build(e.status_code()).json(DeleteAttachmentResponse {
+                success: false,
+                message: e.to_string(),
+            });
+        }
+    };
+    let group_id = membership.group_id;
+
+    // Check that the role of the member allows managing tasks
+    if !membership.can(Permission::ManageTasks) {
+        info!("{} ({}) is not allowed to manage tasks of {}", current_user.user_name, membership.role.as_str(), group_name);
+        return HttpResponse::Forbidden().json(DeleteAttachmentResponse {
+            success: false,
+            message: "Your role in this group does not allow this action".to_string(),
+        });
+    }
+
+    let storage_name = match sqlx::query!(
+        "SELECT storage_name FROM Attachments_ WHERE attachment_id = ? AND group_id = ?",
+        attachment_id, group_id
+    )
+    .fetch_optional(pool.get_ref())
+    .await
+    {
+        Ok(Some(record)) => record.storage_name,
+        Ok(None) => {
+            return HttpResponse::BadRequest().json(DeleteAttachmentResponse {
+                success: false,
+                message: "Attachment not found".to_string(),
+            });
+        }
+        Err(e) => {
+            error!("Failed to fetch attachment {}: {}", attachment_id, e);
+            return HttpResponse::InternalServerError().json(DeleteAttachmentResponse {
+                success: false,
+                message: "Failed to fetch attachment".to_string(),
+            });
+        }
+    };
+
+    let delete_result = sqlx::query!("DELETE FROM Attachments_ WHERE attachment_id = ?", attachment_id)
+        .execute(pool.get_ref())
+        .await;
+
+    match delete_result {
+        Ok(_) => {
+            remove_attachment_files(&[storage_name]).await;
+            HttpResponse::Ok().json(DeleteAttachmentResponse {
+                success: true,
+                message: "Attachment deleted successfully".to_string(),
+            })
+        }
+        Err(e) => {
+            error!("Failed to delete attachment {}: {}", attachment_id, e);
+            HttpResponse::InternalServerError().json(DeleteAttachmentResponse {
+                success: false,
+                message: "Failed to delete attachment".to_string(),
+            })
+        }
+    }
+}
@@ -0,0 +1,169 @@
+use std::collections::HashMap;
+use time::PrimitiveDateTime;
+
+// Dates and progress of a task, keyed by task_id in the maps below
+#[derive(Clone, Copy)]
+pub struct TaskProgress {
+    pub start_time: PrimitiveDateTime,
+    pub end_time: PrimitiveDateTime,
+    pub progress: i32,
+}
+
+// Group task ids by parent, None holding the top level tasks. Children keep the order of `parents`.
+pub fn children_of(parents: &[(i32, Option<i32>)]) -> HashMap<Option<i32>, Vec<i32>> {
+    let mut children: HashMap<Option<i32>, Vec<i32>> = HashMap::new();
+    for (task_id, parent_task_id) in parents {
+        children.entry(*parent_task_id).or_default().push(*task_id);
+    }
+    children
+}
+
+// The task followed by all of its descendants, parents before their children
+pub fn subtree(task_id: i32, children: &HashMap<Option<i32>, Vec<i32>>) -> Vec<i32> {
+    let mut ids = Vec::new();
+    let mut stack = vec![task_id];
+    while let Some(id) = stack.pop() {
+        // a broken hierarchy must not loop forever
+        if ids.contains(&id) {
+            continue;
+        }
+        ids.push(id);
+        if let Some(child_ids) = children.get(&Some(id)) {
+            stack.extend(child_ids.iter().rev());
+        }
+    }
+    ids
+}
+
+// Roll dates and progress up from children to parents.
+// A parent spans its children and its progress is their progress weighted by duration;
+// tasks without children keep their own values.
+pub fn roll_up(
+    tasks: &HashMap<i32, TaskProgress>,
+    children: &HashMap<Option<i32>, Vec<i32>>,
+) -> HashMap<i32, TaskProgress> {
+    let mut rolled: HashMap<i32, TaskProgress> = HashMap::new();
+
+    let mut roots: Vec<i32> = children.get(&None).cloned().unwrap_or_default();
+    roots.sort();
+    for root in roots {
+        // children come after their parent in the subtree, so walk it backwards
+        for task_id in subtree(root, children).into_iter().rev() {
+            let own = match tasks.get(&task_id) {
+                Some(own) => *own,
+                None => continue,
+            };
+            let child_values: Vec<TaskProgress> = children
+                .get(&Some(task_id))
+                .map(|child_ids| child_ids.iter().filter_map(|child_id| rolled.get(child_id).copied()).collect())
+                .unwrap_or_default();
+            rolled.insert(task_id, combine(own, &child_values));
+        }
+    }
+
+    rolled
+}
+
+fn combine(own: TaskProgress, children: &[TaskProgress]) -> TaskProgress {
+    if children.is_empty() {
+        return own;
+    }
+
+    let start_time = children.iter().map(|child| child.start_time).min().unwrap_or(own.start_time);
+    let end_time = children.iter().map(|child| child.end_time).max().unwrap_or(own.end_time);
+
+    let total_seconds: i64 = children.iter().map(|child| (child.end_time - child.start_time).whole_seconds()).sum();
+    let progress = if total_seconds > 0 {
+        let done_seconds: i64 = children
+            .iter()
+            .map(|child| (child.end_time - child.start_time).whole_seconds() * child.progress as i64)
+            .sum();
+        (done_seconds as f64 / total_seconds as f64).round() as i32
+    } else {
+        let sum: i32 = children.iter().map(|child| child.progress).sum();
+        (sum as f64 / children.len() as f64).round() as i32
+    };
+
+    TaskProgress { start_time, end_time, progress }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::datetime;
+    use time::Duration;
+
+    fn progress(start_hour: i64, end_hour: i64, progress: i32) -> TaskProgress {
+        let day = datetime!(2024-01-01 0:00);
+        TaskProgress {
+            start_time: day + Duration::hours(start_hour),
+            end_time: day + Duration::hours(end_hour),
+            progress,
+        }
+    }
+
+    #[test]
+    fn subtree_lists_parents_before_children() {
+        let children = children_of(&[(1, None), (2, Some(1)), (3, Some(2)), (4, Some(1)), (5, None)]);
+        assert_eq!(subtree(1, &children), vec![1, 2, 3, 4]);
+        assert_eq!(subtree(5, &children), vec![5]);
+    }
+
+    #[test]
+    fn subtree_stops_on_a_broken_hierarchy() {
+        let children = children_of(&[(1, Some(2)), (2, Some(1))]);
+        assert_eq!(subtree(1, &children), vec![1, 2]);
+    }
+
+    #[test]
+    fn roll_up_weights_progress_by_duration() {
+        let tasks: HashMap<i32, TaskProgress> =
+            [(1, progress(0, 1, 0)), (2, progress(2, 5, 100)), (3, progress(5, 6, 0))].into_iter().collect();
+        let children = children_of(&[(1, None), (2, Some(1)), (3, Some(1))]);
+
+        let rolled = roll_up(&tasks, &children);
+        let parent = rolled[&1];
+        assert_eq!(parent.start_time, tasks[&2].start_time);
+        assert_eq!(parent.end_time, tasks[&3].end_time);
+        assert_eq!(parent.progress, 75);
+        assert_eq!(rolled[&2].progress, 100);
+    }
+
+    #[test]
+    fn roll_up_replaces_the_stored_window_of_a_parent() {
+        // The parent was planned for 0-10 and marked 90% done, but its subtasks only span 2-6
+        let tasks: HashMap<i32, TaskProgress> =
+            [(1, progress(0, 10, 90)), (2, progress(2, 4, 100)), (3, progress(4, 6, 0))].into_iter().collect();
+        let children = children_of(&[(1, None), (2, Some(1)), (3, Some(1))]);
+
+        let parent = roll_up(&tasks, &children)[&1];
+        assert_eq!(parent.start_time, tasks[&2].start_time);
+        assert_eq!(parent.end_time, tasks[&3].end_time);
+        assert_eq!(parent.progress, 50);
+    }
+
+    #[test]
+    fn roll_up_goes_through_every_level() {
+        // 1 has a leaf (2) and a subtask (3) that is itself split into 4 and 5
+        let tasks: HashMap<i32, TaskProgress> = [
+            (1, progress(0, 1, 0)),
+            (2, progress(0, 2, 100)),
+            (3, progress(0, 1, 0)),
+            (4, progress(2, 4, 100)),
+            (5, progress(4, 8, 25)),
+        ]
+        .into_iter()
+        .collect();
+        let children = children_of(&[(1, None), (2, Some(1)), (3, Some(1)), (4, Some(3)), (5, Some(3))]);
+
+        let rolled = roll_up(&tasks, &children);
+        // 3 spans 2-8 and is (2 * 100 + 4 * 25) / 6 = 50% done
+        assert_eq!(rolled[&3].start_time, tasks[&4].start_time);
+        assert_eq!(rolled[&3].end_time, tasks[&5].end_time);
+        assert_eq!(rolled[&3].progress, 50);
+        // 1 spans 0-8 and weighs 2 (2 hours, 100%) against the rolled-up 3 (6 hours, 50%)
+        assert_eq!(rolled[&1].start_time, tasks[&2].start_time);
+        assert_eq!(rolled[&1].end_time, tasks[&5].end_time);
+        assert_eq!(rolled[&1].progress, 63);
+    }
+}
@@ -0,0 +1,71 @@
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '-' || c == '.'
+}
+
+// User names mentioned as @user_name in a comment, in order of first appearance.
+// A mention must start the text or follow a character that cannot be part of a name,
+// so e-mail addresses are not read as mentions. Trailing dots end the sentence, not the name.
+pub fn parse_mentions(content: &str) -> Vec<String> {
+    let mut mentions: Vec<String> = Vec::new();
+    let mut previous: Option<char> = None;
+    let mut chars = content.char_indices().peekable();
+
+    while let Some((i, c)) = chars.next() {
+        if c == '@' && !previous.is_some_and(is_name_char) {
+            let start = i + c.len_utf8();
+            let mut end = start;
+            while let Some(&(j, next)) = chars.peek() {
+                if !is_name_char(next) {
+                    break;
+                }
+                end = j + next.len_utf8();
+                previous = Some(next);
+                chars.next();
+            }
+
+            let name = content[start..end].trim_end_matches('.');
+            if !name.is_empty() && !mentions.iter().any(|mention| mention == name) {
+                mentions.push(name.to_string());
+            }
+            if end > start {
+                continue;
+            }
+        }
+        previous = Some(c);
+    }
+
+    mentions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mentions_in_order_without_repeats() {
+        assert_eq!(parse_mentions("@alice, ask @bob and @alice"), vec!["alice", "bob"]);
+    }
+
+    #[test]
+    fn email_addresses_are_not_mentions() {
+        assert_eq!(parse_mentions("write to alice@example.com"), Vec::<String>::new());
+        assert_eq!(parse_mentions("cc: bob@example.com @carol"), vec!["carol"]);
+    }
+
+    #[test]
+    fn trailing_dots_end_the_sentence() {
+        assert_eq!(parse_mentions("Thanks @j.doe."), vec!["j.doe"]);
+        assert_eq!(parse_mentions("Over to @bob..."), vec!["bob"]);
+    }
+
+    #[test]
+    fn names_may_hold_underscores_dashes_and_non_ascii_letters() {
+        assert_eq!(parse_mentions("(@under_score-dash) @김철수!"), vec!["under_score-dash", "김철수"]);
+    }
+
+    #[test]
+    fn lone_at_signs_are_ignored() {
+        assert_eq!(parse_mentions("@ meeting at 10 @."), Vec::<String>::new());
+        assert_eq!(parse_mentions("@@bob"), vec!["bob"]);
+    }
+}
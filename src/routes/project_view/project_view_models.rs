@@ -66,23 +66,50 @@ pub struct GetTaskDetailRequest {
     pub owner_user_name: String,
     pub group_name: String,
     pub project_name: String,
+    // only tasks with one of these statuses, all tasks when empty
+    #[serde(default)]
+    pub status_filter: Vec<String>,
 }
 
 #[derive(Serialize)]
 pub struct GetTaskDetailResponse {
-    pub tasks: Vec<Task>,
+    // top level tasks, each with its subtasks
+    pub tasks: Vec<TaskNode>,
+    pub dependencies: Vec<Dependency>,
+}
+
+// Task with its subtasks; a parent's dates and progress are rolled up from its subtasks
+#[derive(Serialize, Deserialize)]
+pub struct TaskNode {
+    #[serde(flatten)]
+    pub task: Task,
+    pub subtasks: Vec<TaskNode>,
 }
 
 // Structs used within the responses
 #[derive(Serialize, Deserialize)]
 pub struct Task {
     pub task_title: String,
+    // first assignee, kept for older clients
     pub worker_name: String,
+    // every assignee of the task
+    pub worker_names: Vec<String>,
     pub start_time: String,
     pub end_time: String,
     pub description: String,
     pub project_name: String,
     pub tag_colors: Vec<String>,
+    // state of the group workflow
+    pub status: String,
+    // percent complete
+    pub progress: i32,
+}
+
+// Dependency edge between two tasks of the same project (prev must finish before next)
+#[derive(Serialize, Deserialize)]
+pub struct Dependency {
+    pub prev_task_title: String,
+    pub next_task_title: String,
 }
 
 
@@ -91,17 +118,34 @@ pub struct AddTaskRequest {
     pub owner_user_name: String,
     pub group_name: String,
     pub project_name: String,
+    // single assignee, used when worker_names is empty
+    #[serde(default)]
     pub worker_name: String,
+    #[serde(default)]
+    pub worker_names: Vec<String>,
     pub task_title: String,
     pub description: String,
     pub start_time: String,
     pub end_time: String,
+    #[serde(default)]
+    pub prev_task_titles: Vec<String>,
+    #[serde(default)]
+    pub next_task_titles: Vec<String>,
+    // first state of the group workflow when empty
+    #[serde(default)]
+    pub status: String,
+    #[serde(default)]
+    pub progress: i32,
+    // top level task when empty
+    #[serde(default)]
+    pub parent_task_title: String,
 }
 
 #[derive(Serialize)]
 pub struct AddTaskResponse {
     pub success: bool,
     pub message: String,
+    pub worker_conflicts: Vec<WorkerConflict>,
 }
 
 
@@ -113,15 +157,56 @@ pub struct UpdateTaskRequest {
     pub task_title: String,
     pub new_task_title: String,
     pub new_worker_name: String,
+    // replaces every assignee when given, an empty list unassigns the task
+    #[serde(default)]
+    pub new_worker_names: Option<Vec<String>>,
     pub new_description: String,
     pub new_start_time: String,
     pub new_end_time: String,
+    // empty keeps the current dependencies of the task
+    #[serde(default)]
+    pub new_prev_task_titles: Vec<String>,
+    #[serde(default)]
+    pub new_next_task_titles: Vec<String>,
+    // push dependent tasks forward instead of rejecting the new schedule
+    #[serde(default)]
+    pub cascade: bool,
+    // empty / missing keeps the current status and progress
+    #[serde(default)]
+    pub new_status: String,
+    #[serde(default)]
+    pub new_progress: Option<i32>,
+    // moves the task under another task, an empty title makes it a top level task
+    #[serde(default)]
+    pub new_parent_task_title: Option<String>,
 }
 
 #[derive(Serialize)]
 pub struct UpdateTaskResponse {
     pub success: bool,
     pub message: String,
+    pub moved_tasks: Vec<MovedTask>,
+    pub worker_conflicts: Vec<WorkerConflict>,
+}
+
+// Task moved by a cascading reschedule, with its new window
+#[derive(Serialize, Deserialize)]
+pub struct MovedTask {
+    pub task_title: String,
+    pub start_time: String,
+    pub end_time: String,
+}
+
+// Another task of an assignee overlapping the added or updated task
+#[derive(Serialize, Deserialize)]
+pub struct WorkerConflict {
+    pub worker_name: String,
+    // None when the caller is not a member of the group of the task
+    pub group_name: Option<String>,
+    pub project_name: Option<String>,
+    pub task_title: Option<String>,
+    pub start_time: String,
+    pub end_time: String,
 }
 
 #[derive(Deserialize)]
@@ -136,4 +221,214 @@ pub struct DeleteTaskRequest {
 pub struct DeleteTaskResponse {
     pub success: bool,
     pub message: String,
-}
\ No newline at end of file
+}
+
+#[derive(Deserialize)]
+pub struct GetDependencyListRequest {
+    pub owner_user_name: String,
+    pub group_name: String,
+    pub project_name: String,
+}
+
+#[derive(Serialize)]
+pub struct GetDependencyListResponse {
+    pub dependencies: Vec<Dependency>,
+}
+
+#[derive(Deserialize)]
+pub struct AddDependencyRequest {
+    pub owner_user_name: String,
+    pub group_name: String,
+    pub project_name: String,
+    pub prev_task_title: String,
+    pub next_task_title: String,
+}
+
+#[derive(Serialize)]
+pub struct AddDependencyResponse {
+    pub success: bool,
+    pub message: String,
+}
+
+#[derive(Deserialize)]
+pub struct DeleteDependencyRequest {
+    pub owner_user_name: String,
+    pub group_name: String,
+    pub project_name: String,
+    pub prev_task_title: String,
+    pub next_task_title: String,
+}
+
+#[derive(Serialize)]
+pub struct DeleteDependencyResponse {
+    pub success: bool,
+    pub message: String,
+}
+
+#[derive(Deserialize)]
+pub struct GetCriticalPathRequest {
+    pub owner_user_name: String,
+    pub group_name: String,
+    pub project_name: String,
+}
+
+#[derive(Serialize)]
+pub struct GetCriticalPathResponse {
+    // Why the critical path could not be computed, empty otherwise
+    pub message: String,
+    pub project_start: String,
+    pub project_finish: String,
+    pub critical_path: Vec<String>,
+    pub tasks: Vec<TaskSchedule>,
+}
+
+// Earliest/latest times and total float of a task, as computed by the critical path method
+#[derive(Serialize, Deserialize)]
+pub struct TaskSchedule {
+    pub task_title: String,
+    pub earliest_start: String,
+    pub earliest_finish: String,
+    pub latest_start: String,
+    pub latest_finish: String,
+    pub total_float_minutes: i64,
+    pub is_critical: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Comment {
+    pub comment_id: i32,
+    pub author_name: String,
+    pub content: String,
+    pub created_at: String,
+    // missing until the comment is edited
+    pub updated_at: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct GetCommentListRequest {
+    pub owner_user_name: String,
+    pub group_name: String,
+    pub project_name: String,
+    pub task_title: String,
+    // oldest comments first, `limit` comments after skipping `offset`
+    #[serde(default)]
+    pub offset: u32,
+    #[serde(default)]
+    pub limit: Option<u32>,
+}
+
+#[derive(Serialize)]
+pub struct GetCommentListResponse {
+    pub comments: Vec<Comment>,
+    // number of comments on the task, for paging
+    pub total_count: i64,
+}
+
+#[derive(Deserialize)]
+pub struct AddCommentRequest {
+    pub owner_user_name: String,
+    pub group_name: String,
+    pub project_name: String,
+    pub task_title: String,
+    pub content: String,
+}
+
+#[derive(Serialize)]
+pub struct AddCommentResponse {
+    pub success: bool,
+    pub message: String,
+    pub comment_id: i32,
+}
+
+#[derive(Deserialize)]
+pub struct UpdateCommentRequest {
+    pub owner_user_name: String,
+    pub group_name: String,
+    pub comment_id: i32,
+    pub new_content: String,
+}
+
+#[derive(Serialize)]
+pub struct UpdateCommentResponse {
+    pub success: bool,
+    pub message: String,
+}
+
+#[derive(Deserialize)]
+pub struct DeleteCommentRequest {
+    pub owner_user_name: String,
+    pub group_name: String,
+    pub comment_id: i32,
+}
+
+#[derive(Serialize)]
+pub struct DeleteCommentResponse {
+    pub success: bool,
+    pub message: String,
+}
+
+// Upload is a multipart form with the text fields owner_user_name, group_name, project_name
+// and optionally task_title, followed by the file in a field named "file"
+#[derive(Serialize)]
+pub struct UploadAttachmentResponse {
+    pub success: bool,
+    pub message: String,
+    pub attachment_id: i32,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Attachment {
+    pub attachment_id: i32,
+    pub file_name: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    // missing for files attached to the project itself
+    pub task_title: Option<String>,
+    pub uploader_name: String,
+    pub created_at: String,
+}
+
+#[derive(Deserialize)]
+pub struct GetAttachmentListRequest {
+    pub owner_user_name: String,
+    pub group_name: String,
+    pub project_name: String,
+    // only the files of this task, every file of the project when empty
+    #[serde(default)]
+    pub task_title: String,
+}
+
+#[derive(Serialize)]
+pub struct GetAttachmentListResponse {
+    pub attachments: Vec<Attachment>,
+    // size of all files of the group against its quota
+    pub used_bytes: i64,
+    pub quota_bytes: i64,
+}
+
+#[derive(Deserialize)]
+pub struct DownloadAttachmentRequest {
+    pub owner_user_name: String,
+    pub group_name: String,
+    pub attachment_id: i32,
+}
+
+// Only sent when the download fails, the file itself is sent otherwise
+#[derive(Serialize)]
+pub struct DownloadAttachmentResponse {
+    pub success: bool,
+    pub message: String,
+}
+
+#[derive(Deserialize)]
+pub struct DeleteAttachmentRequest {
+    pub owner_user_name: String,
+    pub group_name: String,
+    pub attachment_id: i32,
+}
+
+#[derive(Serialize)]
+pub struct DeleteAttachmentResponse {
+    pub success: bool,
+    pub message: String,
+}
@@ -0,0 +1,457 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use time::{Duration, PrimitiveDateTime};
+
+// Scheduled window of a task, keyed by task_id in the maps below
+#[derive(Clone)]
+pub struct TaskWindow {
+    pub title: String,
+    pub start_time: PrimitiveDateTime,
+    pub end_time: PrimitiveDateTime,
+}
+
+// Find a dependency cycle and return the titles along it (first title repeated at the end)
+pub fn find_cycle(tasks: &HashMap<i32, TaskWindow>, edges: &[(i32, i32)]) -> Option<Vec<String>> {
+    let mut successors: HashMap<i32, Vec<i32>> = HashMap::new();
+    for (prev, next) in edges {
+        successors.entry(*prev).or_default().push(*next);
+    }
+
+    // 0 = not visited, 1 = on the current path, 2 = done
+    let mut state: HashMap<i32, u8> = HashMap::new();
+    let mut path: Vec<i32> = Vec::new();
+
+    let mut roots: Vec<i32> = successors.keys().copied().collect();
+    roots.sort();
+
+    for root in roots {
+        if state.get(&root).copied().unwrap_or(0) == 0 {
+            if let Some(cycle) = visit(root, &successors, &mut state, &mut path) {
+                return Some(cycle.into_iter().map(|task_id| title_of(tasks, task_id)).collect());
+            }
+        }
+    }
+
+    None
+}
+
+fn visit(
+    task_id: i32,
+    successors: &HashMap<i32, Vec<i32>>,
+    state: &mut HashMap<i32, u8>,
+    path: &mut Vec<i32>,
+) -> Option<Vec<i32>> {
+    state.insert(task_id, 1);
+    path.push(task_id);
+
+    for next in successors.get(&task_id).into_iter().flatten() {
+        match state.get(next).copied().unwrap_or(0) {
+            0 => {
+                if let Some(cycle) = visit(*next, successors, state, path) {
+                    return Some(cycle);
+                }
+            }
+            1 => {
+                // Back edge: the cycle is the part of the path starting at `next`
+                let start = path.iter().position(|id| id == next).unwrap_or(0);
+                let mut cycle = path[start..].to_vec();
+                cycle.push(*next);
+                return Some(cycle);
+            }
+            _ => {}
+        }
+    }
+
+    path.pop();
+    state.insert(task_id, 2);
+    None
+}
+
+// Return (prev_title, next_title) for every edge where next starts before prev ends.
+// Only edges touching `task_id` are checked when it is given.
+pub fn find_schedule_conflicts(
+    tasks: &HashMap<i32, TaskWindow>,
+    edges: &[(i32, i32)],
+    task_id: Option<i32>,
+) -> Vec<(String, String)> {
+    edges
+        .iter()
+        .filter(|(prev, next)| task_id.is_none_or(|id| *prev == id || *next == id))
+        .filter_map(|(prev, next)| {
+            let prev_task = tasks.get(prev)?;
+            let next_task = tasks.get(next)?;
+            if next_task.start_time < prev_task.end_time {
+                Some((prev_task.title.clone(), next_task.title.clone()))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+// Result of the critical path method for a single task
+pub struct TaskFloat {
+    pub task_id: i32,
+    pub earliest_start: PrimitiveDateTime,
+    pub earliest_finish: PrimitiveDateTime,
+    pub latest_start: PrimitiveDateTime,
+    pub latest_finish: PrimitiveDateTime,
+    pub total_float: Duration,
+}
+
+pub struct CriticalPath {
+    pub project_start: PrimitiveDateTime,
+    pub project_finish: PrimitiveDateTime,
+    // Tasks in topological order
+    pub tasks: Vec<TaskFloat>,
+    // Chain of zero-float tasks from a project start to the project finish
+    pub critical_task_ids: Vec<i32>,
+}
+
+// Order the tasks so that every task comes after all of its predecessors.
+// Returns None when the dependencies contain a cycle.
+fn topological_order(tasks: &HashMap<i32, TaskWindow>, edges: &[(i32, i32)]) -> Option<Vec<i32>> {
+    let mut in_degree: HashMap<i32, usize> = tasks.keys().map(|task_id| (*task_id, 0)).collect();
+    let mut successors: HashMap<i32, Vec<i32>> = HashMap::new();
+    for (prev, next) in edges {
+        if tasks.contains_key(prev) && tasks.contains_key(next) {
+            successors.entry(*prev).or_default().push(*next);
+            *in_degree.entry(*next).or_default() += 1;
+        }
+    }
+
+    // Seed with tasks in start time order so the result is stable
+    let mut roots: Vec<i32> = in_degree.iter().filter(|(_, degree)| **degree == 0).map(|(task_id, _)| *task_id).collect();
+    roots.sort_by_key(|task_id| (tasks[task_id].start_time, *task_id));
+    let mut queue: VecDeque<i32> = roots.into_iter().collect();
+
+    let mut order = Vec::with_capacity(tasks.len());
+    while let Some(task_id) = queue.pop_front() {
+        order.push(task_id);
+        for next in successors.get(&task_id).into_iter().flatten() {
+            let degree = in_degree.get_mut(next).expect("successor is a known task");
+            *degree -= 1;
+            if *degree == 0 {
+                queue.push_back(*next);
+            }
+        }
+    }
+
+    if order.len() == tasks.len() {
+        Some(order)
+    } else {
+        None
+    }
+}
+
+// Critical path method over the scheduled durations of the tasks.
+// Tasks without predecessors may start at the project start (the earliest start_time),
+// tasks without successors may finish at the project finish (the latest earliest finish).
+pub fn compute_critical_path(tasks: &HashMap<i32, TaskWindow>, edges: &[(i32, i32)]) -> Result<Option<CriticalPath>, Vec<String>> {
+    let order = match topological_order(tasks, edges) {
+        Some(order) => order,
+        None => return Err(find_cycle(tasks, edges).unwrap_or_default()),
+    };
+
+    let project_start = match tasks.values().map(|task| task.start_time).min() {
+        Some(start) => start,
+        None => return Ok(None),
+    };
+
+    let duration = |task_id: &i32| tasks[task_id].end_time - tasks[task_id].start_time;
+
+    let mut predecessors: HashMap<i32, Vec<i32>> = HashMap::new();
+    let mut successors: HashMap<i32, Vec<i32>> = HashMap::new();
+    for (prev, next) in edges {
+        if tasks.contains_key(prev) && tasks.contains_key(next) {
+            predecessors.entry(*next).or_default().push(*prev);
+            successors.entry(*prev).or_default().push(*next);
+        }
+    }
+
+    // Forward pass
+    let mut earliest_finish: HashMap<i32, PrimitiveDateTime> = HashMap::new();
+    for task_id in &order {
+        let earliest_start = predecessors
+            .get(task_id)
+            .into_iter()
+            .flatten()
+            .map(|prev| earliest_finish[prev])
+            .max()
+            .unwrap_or(project_start);
+        earliest_finish.insert(*task_id, earliest_start + duration(task_id));
+    }
+
+    let project_finish = earliest_finish.values().copied().max().unwrap_or(project_start);
+
+    // Backward pass
+    let mut latest_start: HashMap<i32, PrimitiveDateTime> = HashMap::new();
+    for task_id in order.iter().rev() {
+        let latest_finish = successors
+            .get(task_id)
+            .into_iter()
+            .flatten()
+            .map(|next| latest_start[next])
+            .min()
+            .unwrap_or(project_finish);
+        latest_start.insert(*task_id, latest_finish - duration(task_id));
+    }
+
+    let floats: Vec<TaskFloat> = order
+        .iter()
+        .map(|task_id| {
+            let earliest_finish = earliest_finish[task_id];
+            let latest_start = latest_start[task_id];
+            let earliest_start = earliest_finish - duration(task_id);
+            TaskFloat {
+                task_id: *task_id,
+                earliest_start,
+                earliest_finish,
+                latest_start,
+                latest_finish: latest_start + duration(task_id),
+                total_float: latest_start - earliest_start,
+            }
+        })
+        .collect();
+
+    // Walk back from the zero-float task finishing last, following at each step the zero-float
+    // predecessor whose finish drives the start, so parallel critical chains are not interleaved
+    let is_critical = |task_id: &i32| latest_start[task_id] == earliest_finish[task_id] - duration(task_id);
+    let mut critical_task_ids = Vec::new();
+    let mut current = order
+        .iter()
+        .find(|task_id| is_critical(task_id) && earliest_finish[*task_id] == project_finish)
+        .copied();
+    while let Some(task_id) = current {
+        critical_task_ids.push(task_id);
+        let earliest_start = earliest_finish[&task_id] - duration(&task_id);
+        current = order
+            .iter()
+            .filter(|prev| predecessors.get(&task_id).is_some_and(|prevs| prevs.contains(prev)))
+            .find(|prev| is_critical(prev) && earliest_finish[*prev] == earliest_start)
+            .copied();
+    }
+    critical_task_ids.reverse();
+
+    Ok(Some(CriticalPath {
+        project_start,
+        project_finish,
+        tasks: floats,
+        critical_task_ids,
+    }))
+}
+
+// Push every task depending (directly or transitively) on `task_id` forward so that it starts
+// no earlier than all of its predecessors end, preserving durations. Tasks are never pulled back.
+// Returns the moved tasks with their new window in topological order; `tasks` is updated in place.
+// Fails with the title of the first task that would be pushed past the latest representable date.
+pub fn cascade_reschedule(
+    tasks: &mut HashMap<i32, TaskWindow>,
+    edges: &[(i32, i32)],
+    task_id: i32,
+) -> Result<Vec<(i32, TaskWindow)>, String> {
+    let order = match topological_order(tasks, edges) {
+        Some(order) => order,
+        None => return Ok(Vec::new()),
+    };
+
+    let mut predecessors: HashMap<i32, Vec<i32>> = HashMap::new();
+    let mut successors: HashMap<i32, Vec<i32>> = HashMap::new();
+    for (prev, next) in edges {
+        predecessors.entry(*next).or_default().push(*prev);
+        successors.entry(*prev).or_default().push(*next);
+    }
+
+    // Collect the tasks reachable from the rescheduled task
+    let mut dependents: HashSet<i32> = HashSet::new();
+    let mut stack = vec![task_id];
+    while let Some(current) = stack.pop() {
+        for next in successors.get(&current).into_iter().flatten() {
+            if dependents.insert(*next) {
+                stack.push(*next);
+            }
+        }
+    }
+
+    let mut moved = Vec::new();
+    for current in order.iter().filter(|current| dependents.contains(current)) {
+        let required_start = predecessors
+            .get(current)
+            .into_iter()
+            .flatten()
+            .filter_map(|prev| tasks.get(prev).map(|task| task.end_time))
+            .max();
+
+        let task = match tasks.get_mut(current) {
+            Some(task) => task,
+            None => continue,
+        };
+
+        if let Some(required_start) = required_start {
+            if task.start_time < required_start {
+                let shift = required_start - task.start_time;
+                let end_time = match task.end_time.checked_add(shift) {
+                    Some(end_time) => end_time,
+                    None => return Err(task.title.clone()),
+                };
+                task.start_time = required_start;
+                task.end_time = end_time;
+                moved.push((*current, task.clone()));
+            }
+        }
+    }
+
+    Ok(moved)
+}
+
+pub fn task_id_by_title(tasks: &HashMap<i32, TaskWindow>, title: &str) -> Option<i32> {
+    tasks
+        .iter()
+        .find(|(_, task)| task.title == title)
+        .map(|(task_id, _)| *task_id)
+}
+
+pub fn describe_cycle(cycle: &[String]) -> String {
+    format!("Dependency cycle detected: {}", cycle.join(" -> "))
+}
+
+pub fn describe_conflicts(conflicts: &[(String, String)]) -> String {
+    let details: Vec<String> = conflicts
+        .iter()
+        .map(|(prev, next)| format!("'{}' starts before its predecessor '{}' ends", next, prev))
+        .collect();
+    format!("Schedule conflict: {}", details.join("; "))
+}
+
+fn title_of(tasks: &HashMap<i32, TaskWindow>, task_id: i32) -> String {
+    match tasks.get(&task_id) {
+        Some(task) => task.title.clone(),
+        None => task_id.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::datetime;
+
+    // Task spanning the given hours of 2024-01-01
+    fn window(title: &str, start_hour: i64, end_hour: i64) -> TaskWindow {
+        let day = datetime!(2024-01-01 0:00);
+        TaskWindow {
+            title: title.to_string(),
+            start_time: day + Duration::hours(start_hour),
+            end_time: day + Duration::hours(end_hour),
+        }
+    }
+
+    fn schedule(windows: &[(i32, &str, i64, i64)]) -> HashMap<i32, TaskWindow> {
+        windows
+            .iter()
+            .map(|(task_id, title, start_hour, end_hour)| (*task_id, window(title, *start_hour, *end_hour)))
+            .collect()
+    }
+
+    #[test]
+    fn find_cycle_accepts_a_dag() {
+        let tasks = schedule(&[(1, "a", 0, 1), (2, "b", 1, 2), (3, "c", 2, 3)]);
+        assert_eq!(find_cycle(&tasks, &[(1, 2), (2, 3), (1, 3)]), None);
+    }
+
+    #[test]
+    fn find_cycle_reports_the_tasks_along_the_cycle() {
+        let tasks = schedule(&[(1, "a", 0, 1), (2, "b", 1, 2), (3, "c", 2, 3)]);
+        let cycle = find_cycle(&tasks, &[(1, 2), (2, 3), (3, 1)]).expect("cycle");
+        assert_eq!(cycle, vec!["a", "b", "c", "a"]);
+    }
+
+    #[test]
+    fn find_cycle_reports_a_self_loop() {
+        let tasks = schedule(&[(1, "a", 0, 1), (2, "b", 1, 2)]);
+        let cycle = find_cycle(&tasks, &[(1, 2), (2, 2)]).expect("cycle");
+        assert_eq!(cycle, vec!["b", "b"]);
+    }
+
+    #[test]
+    fn schedule_conflicts_allow_back_to_back_tasks() {
+        let tasks = schedule(&[(1, "a", 0, 2), (2, "b", 2, 4)]);
+        assert!(find_schedule_conflicts(&tasks, &[(1, 2)], None).is_empty());
+    }
+
+    #[test]
+    fn schedule_conflicts_report_overlapping_successors() {
+        let tasks = schedule(&[(1, "a", 0, 2), (2, "b", 1, 3), (3, "c", 0, 1), (4, "d", 0, 1)]);
+        let edges = [(1, 2), (3, 4)];
+        assert_eq!(
+            find_schedule_conflicts(&tasks, &edges, None),
+            vec![("a".to_string(), "b".to_string()), ("c".to_string(), "d".to_string())]
+        );
+        // Only the edges of the given task are checked
+        assert_eq!(find_schedule_conflicts(&tasks, &edges, Some(4)), vec![("c".to_string(), "d".to_string())]);
+    }
+
+    fn critical_titles(tasks: &HashMap<i32, TaskWindow>, edges: &[(i32, i32)]) -> Vec<String> {
+        let critical_path = compute_critical_path(tasks, edges).expect("no cycle").expect("tasks");
+        critical_path.critical_task_ids.iter().map(|task_id| tasks[task_id].title.clone()).collect()
+    }
+
+    #[test]
+    fn critical_path_follows_the_longest_chain() {
+        // a -> b -> d takes 6 hours, a -> c -> d only 4, so c has float
+        let tasks = schedule(&[(1, "a", 0, 1), (2, "b", 1, 5), (3, "c", 1, 3), (4, "d", 5, 6)]);
+        let edges = [(1, 2), (1, 3), (2, 4), (3, 4)];
+        assert_eq!(critical_titles(&tasks, &edges), vec!["a", "b", "d"]);
+
+        let critical_path = compute_critical_path(&tasks, &edges).unwrap().unwrap();
+        let c = critical_path.tasks.iter().find(|task| task.task_id == 3).unwrap();
+        assert_eq!(c.total_float, Duration::hours(2));
+    }
+
+    #[test]
+    fn critical_path_does_not_interleave_parallel_chains() {
+        // Two independent chains of the same length both have zero float
+        let tasks = schedule(&[(1, "a1", 0, 1), (2, "a2", 1, 2), (3, "b1", 0, 1), (4, "b2", 1, 2)]);
+        let edges = [(1, 2), (3, 4)];
+
+        let critical_path = compute_critical_path(&tasks, &edges).unwrap().unwrap();
+        assert!(critical_path.tasks.iter().all(|task| task.total_float.is_zero()));
+
+        let titles = critical_titles(&tasks, &edges);
+        assert!(titles == ["a1", "a2"] || titles == ["b1", "b2"], "got {:?}", titles);
+    }
+
+    #[test]
+    fn critical_path_reports_cycles() {
+        let tasks = schedule(&[(1, "a", 0, 1), (2, "b", 1, 2)]);
+        let cycle = compute_critical_path(&tasks, &[(1, 2), (2, 1)]).err().expect("cycle");
+        assert_eq!(describe_cycle(&cycle), "Dependency cycle detected: a -> b -> a");
+    }
+
+    #[test]
+    fn critical_path_of_an_empty_project() {
+        assert!(compute_critical_path(&HashMap::new(), &[]).unwrap().is_none());
+    }
+
+    #[test]
+    fn cascade_pushes_dependents_and_keeps_durations() {
+        // a now ends at 4: b (2-3) moves to 4-5, which pushes c (4-6) to 5-7; d (8-9) has room
+        let mut tasks = schedule(&[(1, "a", 0, 4), (2, "b", 2, 3), (3, "c", 4, 6), (4, "d", 8, 9)]);
+        let moved = cascade_reschedule(&mut tasks, &[(1, 2), (2, 3), (3, 4)], 1).expect("in range");
+
+        let moved_titles: Vec<&str> = moved.iter().map(|(_, task)| task.title.as_str()).collect();
+        assert_eq!(moved_titles, vec!["b", "c"]);
+        for (task_id, start_hour, end_hour) in [(2, 4, 5), (3, 5, 7), (4, 8, 9)] {
+            let expected = window("", start_hour, end_hour);
+            assert_eq!((tasks[&task_id].start_time, tasks[&task_id].end_time), (expected.start_time, expected.end_time));
+        }
+    }
+
+    #[test]
+    fn cascade_rejects_pushing_a_task_past_the_last_date() {
+        let last = PrimitiveDateTime::MAX;
+        let mut tasks = HashMap::from([
+            (1, TaskWindow { title: "a".to_string(), start_time: last - Duration::hours(3), end_time: last - Duration::hours(1) }),
+            (2, TaskWindow { title: "b".to_string(), start_time: last - Duration::hours(2), end_time: last }),
+        ]);
+        assert_eq!(cascade_reschedule(&mut tasks, &[(1, 2)], 1).err(), Some("b".to_string()));
+    }
+}
@@ -0,0 +1,31 @@
+use std::path::PathBuf;
+use log::error;
+
+// Directory holding the attachment files, from ATTACHMENT_DIR
+pub fn attachment_dir() -> PathBuf {
+    PathBuf::from(std::env::var("ATTACHMENT_DIR").unwrap_or_else(|_| "attachments".to_string()))
+}
+
+pub fn attachment_path(storage_name: &str) -> PathBuf {
+    attachment_dir().join(storage_name)
+}
+
+// Keep only the last component of an uploaded file name, so it is safe to show and send back
+pub fn clean_file_name(file_name: &str) -> String {
+    let base = file_name.rsplit(['/', '\\']).next().unwrap_or_default().trim();
+    let cleaned: String = base.chars().filter(|c| !c.is_control()).take(255).collect();
+    if cleaned.is_empty() || cleaned == "." || cleaned == ".." {
+        "attachment".to_string()
+    } else {
+        cleaned
+    }
+}
+
+// Remove attachment files whose rows are gone, logging the files that could not be removed
+pub async fn remove_attachment_files(storage_names: &[String]) {
+    for storage_name in storage_names {
+        if let Err(e) = tokio::fs::remove_file(attachment_path(storage_name)).await {
+            error!("Failed to remove attachment file {}: {}", storage_name, e);
+        }
+    }
+}
@@ -2,10 +2,18 @@
 
 use actix_web::web;
 
+use super::admin::admin_handlers;
+use super::admin::admin_middleware::AdminGuard;
+
 pub fn admin_configure(cfg: &mut web::ServiceConfig) {
     cfg.service(
         web::scope("/admin")
-            .route("/delete/all/the/sessions/BECAREFUL", web::get().to(super::admin::admin_handlers::session_reset))
+            .wrap(AdminGuard)
+            .route("/delete/all/the/sessions/BECAREFUL", web::get().to(admin_handlers::session_reset))
+            .route("/user-list", web::get().to(admin_handlers::get_user_list))
+            .route("/disable-user", web::post().to(admin_handlers::disable_user))
+            .route("/purge-user-sessions", web::post().to(admin_handlers::purge_user_sessions))
+            .route("/set-attachment-quota", web::post().to(admin_handlers::set_attachment_quota))
     );
 }
 
@@ -22,6 +30,9 @@ pub fn login_configure(cfg: &mut web::ServiceConfig) {
             .route("/login", web::post().to(login_handlers::login))
             .route("/auto-login", web::post().to(login_handlers::auto_login))
             .route("/logout", web::post().to(login_handlers::logout))
+            .route("/session-list", web::post().to(login_handlers::get_session_list))
+            .route("/revoke-session", web::delete().to(login_handlers::revoke_session))
+            .route("/revoke-other-sessions", web::delete().to(login_handlers::revoke_other_sessions))
     );
 }
 
@@ -36,6 +47,12 @@ pub fn group_selection_configure(cfg: &mut web::ServiceConfig) {
             .route("/add-group", web::post().to(group_selection_handlers::add_group))
             .route("/update-group", web::patch().to(group_selection_handlers::update_group))
             .route("/delete-group", web::delete().to(group_selection_handlers::delete_group))
+            .route("/transfer-ownership", web::patch().to(group_selection_handlers::transfer_ownership))
+            .route("/invitation-list", web::post().to(group_selection_handlers::get_invitation_list))
+            .route("/accept-invitation", web::post().to(group_selection_handlers::accept_invitation))
+            .route("/decline-invitation", web::post().to(group_selection_handlers::decline_invitation))
+            .route("/join-group", web::post().to(group_selection_handlers::join_group))
+            .route("/my-task-list", web::post().to(group_selection_handlers::get_my_task_list))
     );
 }
 
@@ -48,6 +65,14 @@ pub fn group_view_configure(cfg: &mut web::ServiceConfig) {
             .route("/", web::get().to(group_view_handlers::group_view_get))  // Add this line
             .route("/worker-list", web::post().to(group_view_handlers::get_worker_list))
             .route("/add-worker", web::post().to(group_view_handlers::add_worker))
+            .route("/remove-worker", web::delete().to(group_view_handlers::remove_worker))
+            .route("/update-worker-role", web::patch().to(group_view_handlers::update_worker_role))
+            .route("/leave-group", web::delete().to(group_view_handlers::leave_group))
+            .route("/invite-link-list", web::post().to(group_view_handlers::get_invite_link_list))
+            .route("/add-invite-link", web::post().to(group_view_handlers::add_invite_link))
+            .route("/delete-invite-link", web::delete().to(group_view_handlers::delete_invite_link))
+            .route("/workflow", web::post().to(group_view_handlers::get_workflow))
+            .route("/update-workflow", web::patch().to(group_view_handlers::update_workflow))
             .route("/tag-list", web::post().to(group_view_handlers::get_tag_list))
             .route("/add-tag", web::post().to(group_view_handlers::add_tag))
             .route("/update-tag", web::patch().to(group_view_handlers::update_tag))
@@ -55,6 +80,7 @@ pub fn group_view_configure(cfg: &mut web::ServiceConfig) {
             .route("/task-list/by-tag-list", web::post().to(group_view_handlers::get_task_list_by_tag_list))
             .route("/task-list/by-project-name", web::post().to(group_view_handlers::get_task_list_by_project_name))
             .route("/project-list", web::post().to(group_view_handlers::get_project_list))
+            .route("/workload-report", web::post().to(group_view_handlers::get_workload_report))
     );
 }
 
@@ -70,8 +96,33 @@ pub fn project_view_configure(cfg: &mut web::ServiceConfig) {
             .route("/update-project", web::patch().to(project_view_handlers::update_project))
             .route("/delete-project", web::delete().to(project_view_handlers::delete_project))
             .route("/task-detail", web::post().to(project_view_handlers::get_task_detail))
+            .route("/critical-path", web::post().to(project_view_handlers::get_critical_path))
             .route("/add-task", web::post().to(project_view_handlers::add_task))
             .route("/update-task", web::patch().to(project_view_handlers::update_task))
             .route("/delete-task", web::delete().to(project_view_handlers::delete_task))
+            .route("/dependency-list", web::post().to(project_view_handlers::get_dependency_list))
+            .route("/add-dependency", web::post().to(project_view_handlers::add_dependency))
+            .route("/delete-dependency", web::delete().to(project_view_handlers::delete_dependency))
+            .route("/comment-list", web::post().to(project_view_handlers::get_comment_list))
+            .route("/add-comment", web::post().to(project_view_handlers::add_comment))
+            .route("/update-comment", web::patch().to(project_view_handlers::update_comment))
+            .route("/delete-comment", web::delete().to(project_view_handlers::delete_comment))
+            .route("/attachment-list", web::post().to(project_view_handlers::get_attachment_list))
+            .route("/upload-attachment", web::post().to(project_view_handlers::upload_attachment))
+            .route("/download-attachment", web::post().to(project_view_handlers::download_attachment))
+            .route("/delete-attachment", web::delete().to(project_view_handlers::delete_attachment))
+    );
+}
+
+use super::notification::notification_handlers;
+
+pub fn notification_configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/api-notification")
+            .route("", web::get().to(notification_handlers::notification_get))
+            .route("/", web::get().to(notification_handlers::notification_get))
+            .route("/notification-list", web::post().to(notification_handlers::get_notification_list))
+            .route("/mark-read", web::patch().to(notification_handlers::mark_read))
+            .route("/mark-all-read", web::patch().to(notification_handlers::mark_all_read))
     );
 }
\ No newline at end of file